@@ -3,10 +3,245 @@ use crate::{
     _impl::arrow::datatypes::{DataType, Field, UnionMode},
     internal::{
         error::{error, fail, Error, Result},
-        schema::{GenericDataType, GenericField, Strategy, STRATEGY_KEY},
+        schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy, STRATEGY_KEY},
     },
 };
 
+/// Replace the data type and strategy of a nested field
+///
+/// The `path` argument should be a dotted path to the target field, e.g.,
+/// `"parent.child.subchild"`. As the fields of arrow-rs are immutable, the
+/// fields along the path are rebuilt. List items are skipped in the path, i.e.,
+/// the fields of a list of structs are addressed as `"list.field"`, and maps
+/// are addressed via their entries, e.g., `"map.value"`. The strategy is stored
+/// in the metadata of the field or removed if `None`.
+///
+/// Example:
+///
+/// ```rust
+/// # use serde_arrow::_impl::arrow::datatypes::{DataType, Field};
+/// # use chrono::NaiveDateTime;
+/// # use serde::Serialize;
+/// #
+/// use serde_arrow::{
+///     arrow::{serialize_into_fields, experimental},
+///     schema::{Strategy, TracingOptions},
+/// };
+///
+/// ##[derive(Serialize, Default)]
+/// struct Outer {
+///     a: u32,
+///     b: Nested,
+/// }
+///
+/// ##[derive(Serialize, Default)]
+/// struct Nested {
+///     dt: NaiveDateTime,
+/// }
+///
+/// let mut fields = serialize_into_fields(
+///     &[Outer::default()],
+///     TracingOptions::default(),
+/// ).unwrap();
+///
+/// experimental::set_field_type(
+///     &mut fields,
+///     "b.dt",
+///     DataType::Date64,
+///     Some(Strategy::NaiveStrAsDate64),
+/// ).unwrap();
+/// ```
+pub fn set_field_type(
+    fields: &mut Vec<Field>,
+    path: &str,
+    data_type: DataType,
+    strategy: Option<Strategy>,
+) -> Result<()> {
+    let (parent, name) = split_path(path)?;
+    let mut data_type = Some(data_type);
+    modify_children(fields, &parent, &mut |children| {
+        let idx = find_field_index(children, name)?;
+        let field = &children[idx];
+
+        let mut metadata = field.metadata().clone();
+        match &strategy {
+            Some(strategy) => metadata.insert(STRATEGY_KEY.to_string(), strategy.to_string()),
+            None => metadata.remove(STRATEGY_KEY),
+        };
+        let data_type = data_type
+            .take()
+            .ok_or_else(|| error!("Data type already used"))?;
+
+        children[idx] =
+            Field::new(field.name(), data_type, field.is_nullable()).with_metadata(metadata);
+        Ok(())
+    })
+}
+
+/// Insert a field as the last child of the field at `parent`
+///
+/// An empty `parent` inserts the field among the top-level fields. See
+/// [`set_field_type`] for the format of the path.
+///
+pub fn insert_field(fields: &mut Vec<Field>, parent: &str, field: Field) -> Result<()> {
+    let parent = if parent.is_empty() {
+        Vec::new()
+    } else {
+        parent.split('.').collect()
+    };
+    let mut field = Some(field);
+    modify_children(fields, &parent, &mut |children| {
+        let field = field
+            .take()
+            .ok_or_else(|| error!("Field already inserted"))?;
+        if children.iter().any(|child| child.name() == field.name()) {
+            fail!(
+                "Cannot insert field {}: the field already exists",
+                field.name()
+            );
+        }
+        children.push(field);
+        Ok(())
+    })
+}
+
+/// Remove the field at the given path and return it
+///
+/// See [`set_field_type`] for the format of the path.
+///
+pub fn remove_field(fields: &mut Vec<Field>, path: &str) -> Result<Field> {
+    let (parent, name) = split_path(path)?;
+    let mut removed = None;
+    modify_children(fields, &parent, &mut |children| {
+        let idx = find_field_index(children, name)?;
+        removed = Some(children.remove(idx));
+        Ok(())
+    })?;
+    removed.ok_or_else(|| error!("Cannot find field {name}"))
+}
+
+/// Rename the field at the given path
+///
+/// See [`set_field_type`] for the format of the path.
+///
+pub fn rename_field(fields: &mut Vec<Field>, path: &str, new_name: &str) -> Result<()> {
+    let (parent, name) = split_path(path)?;
+    modify_children(fields, &parent, &mut |children| {
+        let idx = find_field_index(children, name)?;
+        if children.iter().any(|child| child.name() == new_name) {
+            fail!("Cannot rename field {name} to {new_name}: the field already exists");
+        }
+        children[idx] = rebuild_field(&children[idx], new_name, children[idx].data_type().clone());
+        Ok(())
+    })
+}
+
+fn split_path(path: &str) -> Result<(Vec<&str>, &str)> {
+    if path.is_empty() {
+        fail!("Cannot get field with empty path");
+    }
+    let mut parent = path.split('.').collect::<Vec<_>>();
+    let name = parent.pop().unwrap();
+    Ok((parent, name))
+}
+
+fn find_field_index(fields: &[Field], name: &str) -> Result<usize> {
+    fields
+        .iter()
+        .position(|f| f.name() == name)
+        .ok_or_else(|| error!("Cannot find field {name}"))
+}
+
+fn rebuild_field(field: &Field, name: &str, data_type: DataType) -> Field {
+    Field::new(name, data_type, field.is_nullable()).with_metadata(field.metadata().clone())
+}
+
+/// Apply `op` to the children of the field at `path` and rebuild the fields
+/// along the path
+///
+fn modify_children(
+    fields: &mut Vec<Field>,
+    path: &[&str],
+    op: &mut dyn FnMut(&mut Vec<Field>) -> Result<()>,
+) -> Result<()> {
+    let Some((head, tail)) = path.split_first() else {
+        return op(fields);
+    };
+
+    let idx = find_field_index(fields, head)?;
+    let data_type = modify_nested_fields(fields[idx].data_type(), &mut |children| {
+        modify_children(children, tail, op)
+    })?;
+    fields[idx] = rebuild_field(&fields[idx], head, data_type);
+    Ok(())
+}
+
+fn modify_nested_fields(
+    data_type: &DataType,
+    op: &mut dyn FnMut(&mut Vec<Field>) -> Result<()>,
+) -> Result<DataType> {
+    let modify_item = |item: &Field, op: &mut dyn FnMut(&mut Vec<Field>) -> Result<()>| {
+        let data_type = modify_nested_fields(item.data_type(), op)?;
+        Ok::<_, Error>(rebuild_field(item, item.name(), data_type))
+    };
+
+    match data_type {
+        DataType::Struct(fields) => {
+            let mut fields = fields
+                .iter()
+                .map(|f| f.as_field_ref().clone())
+                .collect::<Vec<_>>();
+            op(&mut fields)?;
+            Ok(DataType::Struct(fields.into_iter().collect()))
+        }
+        DataType::List(item) => Ok(DataType::List(
+            Box::new(modify_item(item.as_ref(), op)?).into(),
+        )),
+        DataType::LargeList(item) => Ok(DataType::LargeList(
+            Box::new(modify_item(item.as_ref(), op)?).into(),
+        )),
+        DataType::FixedSizeList(item, n) => Ok(DataType::FixedSizeList(
+            Box::new(modify_item(item.as_ref(), op)?).into(),
+            *n,
+        )),
+        DataType::Map(entries, sorted) => Ok(DataType::Map(
+            Box::new(modify_item(entries.as_ref(), op)?).into(),
+            *sorted,
+        )),
+        DataType::Dictionary(key, value) => Ok(DataType::Dictionary(
+            key.clone(),
+            Box::new(modify_nested_fields(value, op)?),
+        )),
+        #[cfg(not(any(feature = "arrow-35", feature = "arrow-36")))]
+        DataType::Union(union_fields, mode) => {
+            let (type_ids, mut fields): (Vec<_>, Vec<_>) = union_fields
+                .iter()
+                .map(|(idx, field)| (idx, field.as_ref().clone()))
+                .unzip();
+            op(&mut fields)?;
+            if fields.len() != type_ids.len() {
+                fail!("Cannot change the number of variants of a union");
+            }
+            let union_fields = type_ids
+                .into_iter()
+                .zip(fields)
+                .map(|(idx, field)| (idx, std::sync::Arc::new(field)))
+                .collect();
+            Ok(DataType::Union(union_fields, *mode))
+        }
+        #[cfg(any(feature = "arrow-35", feature = "arrow-36"))]
+        DataType::Union(fields, type_ids, mode) => {
+            let mut fields = fields.clone();
+            op(&mut fields)?;
+            if fields.len() != type_ids.len() {
+                fail!("Cannot change the number of variants of a union");
+            }
+            Ok(DataType::Union(fields, type_ids.clone(), *mode))
+        }
+        dt => fail!("Data type {dt} does not support nested fields"),
+    }
+}
+
 impl TryFrom<&DataType> for GenericDataType {
     type Error = Error;
 
@@ -27,6 +262,7 @@ impl TryFrom<&DataType> for GenericDataType {
             DataType::Float64 => Ok(GenericDataType::F64),
             DataType::Utf8 => Ok(GenericDataType::Utf8),
             DataType::LargeUtf8 => Ok(GenericDataType::LargeUtf8),
+            DataType::Date32 => Ok(GenericDataType::Date32),
             DataType::Date64 => Ok(GenericDataType::Date64),
             _ => fail!("Only primitive data types can be converted to GenericDataType"),
         }
@@ -136,6 +372,7 @@ impl TryFrom<&GenericField> for Field {
             GenericDataType::F16 => DataType::Float16,
             GenericDataType::F32 => DataType::Float32,
             GenericDataType::F64 => DataType::Float64,
+            GenericDataType::Date32 => DataType::Date32,
             GenericDataType::Date64 => DataType::Date64,
             GenericDataType::Utf8 => DataType::Utf8,
             GenericDataType::LargeUtf8 => DataType::LargeUtf8,
@@ -230,3 +467,35 @@ impl TryFrom<&GenericField> for Field {
         Ok(field)
     }
 }
+
+impl SerdeArrowSchema {
+    /// Build a schema from `arrow` fields
+    pub fn from_arrow_fields(fields: &[Field]) -> Result<Self> {
+        Self::try_from(fields)
+    }
+
+    /// Convert the schema into `arrow` fields
+    pub fn to_arrow_fields(&self) -> Result<Vec<Field>> {
+        Vec::<Field>::try_from(self)
+    }
+}
+
+impl TryFrom<&[Field]> for SerdeArrowSchema {
+    type Error = Error;
+
+    fn try_from(fields: &[Field]) -> Result<Self> {
+        let fields = fields
+            .iter()
+            .map(GenericField::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { fields })
+    }
+}
+
+impl TryFrom<&SerdeArrowSchema> for Vec<Field> {
+    type Error = Error;
+
+    fn try_from(schema: &SerdeArrowSchema) -> Result<Self> {
+        schema.fields.iter().map(Field::try_from).collect()
+    }
+}
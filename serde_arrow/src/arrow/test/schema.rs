@@ -0,0 +1,170 @@
+use serde::Serialize;
+
+use crate::{
+    _impl::arrow::datatypes::{DataType, Field},
+    arrow::{
+        experimental::{insert_field, remove_field, rename_field, set_field_type},
+        serialize_into_arrays, serialize_into_fields,
+    },
+    schema::{Strategy, TracingOptions, STRATEGY_KEY},
+};
+
+#[derive(Serialize)]
+struct Outer {
+    a: u32,
+    b: Nested,
+    c: Vec<Nested>,
+}
+
+#[derive(Serialize)]
+struct Nested {
+    dt: String,
+    value: i8,
+}
+
+fn example() -> Vec<Outer> {
+    vec![Outer {
+        a: 1,
+        b: Nested {
+            dt: String::from("2023-01-31T12:00:00"),
+            value: 2,
+        },
+        c: vec![Nested {
+            dt: String::from("2023-01-31T13:00:00"),
+            value: 3,
+        }],
+    }]
+}
+
+fn traced_fields() -> Vec<Field> {
+    serialize_into_fields(&example(), TracingOptions::default()).unwrap()
+}
+
+fn struct_fields(data_type: &DataType) -> Vec<Field> {
+    match data_type {
+        DataType::Struct(fields) => fields.iter().map(|f| f.as_ref().clone()).collect(),
+        DataType::LargeList(item) => struct_fields(item.data_type()),
+        dt => panic!("unexpected data type {dt}"),
+    }
+}
+
+#[test]
+fn set_nested_field_type() {
+    let mut fields = traced_fields();
+    set_field_type(
+        &mut fields,
+        "b.dt",
+        DataType::Date64,
+        Some(Strategy::NaiveStrAsDate64),
+    )
+    .unwrap();
+    set_field_type(
+        &mut fields,
+        "c.dt",
+        DataType::Date64,
+        Some(Strategy::NaiveStrAsDate64),
+    )
+    .unwrap();
+
+    let expected =
+        Field::new("dt", DataType::Date64, false).with_metadata(Strategy::NaiveStrAsDate64.into());
+    assert_eq!(struct_fields(fields[1].data_type())[0], expected);
+    assert_eq!(struct_fields(fields[2].data_type())[0], expected);
+
+    let arrays = serialize_into_arrays(&fields, &example()).unwrap();
+    assert_eq!(arrays.len(), 3);
+}
+
+#[test]
+fn set_field_type_removes_strategy() {
+    let mut fields = vec![
+        Field::new("a", DataType::Date64, false).with_metadata(Strategy::UtcStrAsDate64.into())
+    ];
+    set_field_type(&mut fields, "a", DataType::LargeUtf8, None).unwrap();
+
+    assert_eq!(fields[0].data_type(), &DataType::LargeUtf8);
+    assert!(!fields[0].metadata().contains_key(STRATEGY_KEY));
+}
+
+#[test]
+fn insert_nested_field() {
+    let mut fields = traced_fields();
+    insert_field(
+        &mut fields,
+        "b",
+        Field::new("extra", DataType::Boolean, true),
+    )
+    .unwrap();
+    insert_field(&mut fields, "", Field::new("d", DataType::Int64, true)).unwrap();
+
+    let names = struct_fields(fields[1].data_type())
+        .iter()
+        .map(|f| f.name().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["dt", "value", "extra"]);
+    assert_eq!(fields[3].name(), "d");
+
+    let err = insert_field(&mut fields, "b", Field::new("dt", DataType::Null, true)).unwrap_err();
+    assert!(err.to_string().contains("already exists"), "{err}");
+}
+
+#[test]
+fn remove_nested_field() {
+    let mut fields = traced_fields();
+    let removed = remove_field(&mut fields, "c.value").unwrap();
+    assert_eq!(removed, Field::new("value", DataType::Int8, false));
+
+    let names = struct_fields(fields[2].data_type())
+        .iter()
+        .map(|f| f.name().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["dt"]);
+
+    assert!(remove_field(&mut fields, "c.value").is_err());
+}
+
+#[test]
+fn rename_nested_field() {
+    let mut fields = traced_fields();
+    rename_field(&mut fields, "b.value", "renamed").unwrap();
+    rename_field(&mut fields, "a", "id").unwrap();
+
+    assert_eq!(fields[0].name(), "id");
+    assert_eq!(
+        struct_fields(fields[1].data_type())[1],
+        Field::new("renamed", DataType::Int8, false)
+    );
+    assert!(rename_field(&mut fields, "b.dt", "renamed").is_err());
+}
+
+#[test]
+fn maps_and_invalid_paths() {
+    let entries = Field::new(
+        "entries",
+        DataType::Struct(
+            vec![
+                Field::new("key", DataType::LargeUtf8, false),
+                Field::new("value", DataType::Int32, false),
+            ]
+            .into(),
+        ),
+        false,
+    );
+    let mut fields = vec![
+        Field::new("map", DataType::Map(Box::new(entries).into(), false), false),
+        Field::new("a", DataType::Int32, false),
+    ];
+
+    set_field_type(&mut fields, "map.value", DataType::Int64, None).unwrap();
+    let DataType::Map(entries, _) = fields[0].data_type() else {
+        panic!();
+    };
+    assert_eq!(
+        struct_fields(entries.data_type())[1],
+        Field::new("value", DataType::Int64, false)
+    );
+
+    assert!(set_field_type(&mut fields, "", DataType::Int64, None).is_err());
+    assert!(set_field_type(&mut fields, "missing", DataType::Int64, None).is_err());
+    assert!(set_field_type(&mut fields, "a.b", DataType::Int64, None).is_err());
+}
@@ -151,3 +151,61 @@ test!(
         vec![]
     ]
 );
+
+#[test]
+fn overwrite_fields() {
+    use crate::{
+        _impl::arrow::datatypes::{DataType, Field},
+        arrow::{serialize_into_arrays, serialize_into_fields},
+        schema::Strategy,
+    };
+
+    #[derive(Serialize)]
+    struct Record {
+        dt: String,
+    }
+
+    let dt_field =
+        Field::new("dt", DataType::Date64, false).with_metadata(Strategy::UtcStrAsDate64.into());
+    let options = TracingOptions::default()
+        .overwrite("dt", &dt_field)
+        .unwrap();
+
+    let items = [Record {
+        dt: String::from("2023-01-31T12:00:00Z"),
+    }];
+    let fields = serialize_into_fields(&items, options).unwrap();
+    assert_eq!(fields, vec![dt_field]);
+
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    assert_eq!(arrays[0].data_type(), &DataType::Date64);
+}
+
+#[test]
+fn serde_arrow_schema_roundtrip() {
+    use crate::{
+        _impl::arrow::datatypes::{DataType, Field},
+        arrow::serialize_into_fields,
+        schema::SerdeArrowSchema,
+    };
+
+    #[derive(Serialize)]
+    struct Record {
+        a: Option<u32>,
+        b: Vec<String>,
+    }
+
+    let items = [Record {
+        a: Some(1),
+        b: vec![String::from("x")],
+    }];
+    let fields = serialize_into_fields(&items, TracingOptions::default()).unwrap();
+
+    let schema = SerdeArrowSchema::from_samples(&items, TracingOptions::default()).unwrap();
+    assert_eq!(schema.to_arrow_fields().unwrap(), fields);
+    assert_eq!(
+        SerdeArrowSchema::from_arrow_fields(&fields).unwrap(),
+        schema
+    );
+    assert_eq!(fields[0], Field::new("a", DataType::UInt32, true));
+}
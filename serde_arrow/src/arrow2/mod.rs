@@ -12,6 +12,8 @@ mod type_support;
 #[cfg(test)]
 mod test;
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -22,7 +24,10 @@ use crate::{
         schema::{GenericField, TracingOptions},
         serialization::{compile_serialization, CompilationOptions, Interpreter},
         sink::serialize_into_sink,
-        source::{deserialize_from_source, AddOuterSequenceSource},
+        source::{
+            deserialize_from_source, deserialize_from_source_with_options, AddOuterSequenceSource,
+            DeserializationOptions,
+        },
     },
 };
 
@@ -165,6 +170,51 @@ where
     deserialize_from_source(source)
 }
 
+/// Deserialize a type from the given arrays with the given options
+///
+/// With `format_numbers_as_strings`, fields serialized with the `StrAsNumber`
+/// strategy can be deserialized back into strings:
+///
+/// ```rust
+/// # use serde_arrow::_impl::arrow2 as arrow2;
+/// use arrow2::datatypes::{DataType, Field};
+/// use serde::{Deserialize, Serialize};
+/// use serde_arrow::{
+///     arrow2::{deserialize_from_arrays_with_options, serialize_into_arrays},
+///     experimental::DeserializationOptions,
+///     schema::Strategy,
+/// };
+///
+/// ##[derive(Debug, PartialEq, Deserialize, Serialize)]
+/// struct Record {
+///     a: String,
+/// }
+///
+/// let fields = vec![
+///     Field::new("a", DataType::Int64, false).with_metadata(Strategy::StrAsNumber.into()),
+/// ];
+/// let items = vec![Record { a: String::from("42") }];
+/// let arrays = serialize_into_arrays(&fields, &items).unwrap();
+///
+/// let options = DeserializationOptions::default().format_numbers_as_strings(true);
+/// let roundtripped: Vec<Record> =
+///     deserialize_from_arrays_with_options(&fields, &arrays, options).unwrap();
+/// assert_eq!(roundtripped, items);
+/// ```
+///
+pub fn deserialize_from_arrays_with_options<'de, T, A>(
+    fields: &'de [Field],
+    arrays: &'de [A],
+    options: DeserializationOptions,
+) -> Result<T>
+where
+    T: Deserialize<'de>,
+    A: AsRef<dyn Array>,
+{
+    let source = build_record_source(fields, arrays)?;
+    deserialize_from_source_with_options(source, options)
+}
+
 /// Determine the schema of an object that represents a single array
 ///
 /// Example:
@@ -188,6 +238,133 @@ where
     (&field).try_into()
 }
 
+/// Determine the schema (as a list of fields) for the given record type
+///
+/// In contrast to [serialize_into_fields], no sample records are required. The
+/// schema is determined from the `Deserialize` implementation of the type. All
+/// enum variants are included and `Option<T>` fields are marked as nullable
+/// with the type of `T`.
+///
+/// Limitations:
+///
+/// - self describing types (e.g., untagged enums, internally tagged enums,
+///   flattened structs, or `serde_json::Value`) are not supported
+/// - recursive types are not supported
+/// - types that validate their values during deserialization may fail, as
+///   default values (zero, empty strings) are used as samples. The `chrono`
+///   date and time types are supported and are traced with sample strings
+///   that are detected by `guess_dates`
+/// - maps are always traced as arrow maps, as their keys are not known
+///
+/// Example:
+///
+/// ```rust
+/// # use serde_arrow::_impl::arrow2 as arrow2;
+/// #
+/// use arrow2::datatypes::{DataType, Field};
+/// use serde::Deserialize;
+/// use serde_arrow::arrow2::serialize_into_fields_from_type;
+///
+/// ##[derive(Deserialize)]
+/// struct Record {
+///     a: Option<f32>,
+///     b: u64,
+/// }
+///
+/// let fields = serialize_into_fields_from_type::<Record>(Default::default()).unwrap();
+/// let expected = vec![
+///     Field::new("a", DataType::Float32, true),
+///     Field::new("b", DataType::UInt64, false),
+/// ];
+///
+/// assert_eq!(fields, expected);
+/// ```
+///
+pub fn serialize_into_fields_from_type<'de, T>(options: TracingOptions) -> Result<Vec<Field>>
+where
+    T: Deserialize<'de>,
+{
+    internal::serialize_into_fields_from_type::<T>(options)?
+        .iter()
+        .map(|f| f.try_into())
+        .collect()
+}
+
+/// Determine the schema of a single array from the type of its elements
+///
+/// See [serialize_into_fields_from_type] for details.
+///
+/// Example:
+///
+/// ```rust
+/// # use serde_arrow::_impl::arrow2 as arrow2;
+/// use arrow2::datatypes::{DataType, Field};
+/// use serde_arrow::arrow2::serialize_into_field_from_type;
+///
+/// let field = serialize_into_field_from_type::<f32>("floats", Default::default()).unwrap();
+/// assert_eq!(field, Field::new("floats", DataType::Float32, false));
+/// ```
+///
+pub fn serialize_into_field_from_type<'de, T>(name: &str, options: TracingOptions) -> Result<Field>
+where
+    T: Deserialize<'de>,
+{
+    let field = internal::serialize_into_field_from_type::<T>(name, options)?;
+    (&field).try_into()
+}
+
+/// Merge the schemas (as lists of fields) traced from different sources
+///
+/// Fields are matched by name and merged recursively:
+///
+/// - the nullability of fields is combined, fields missing in any of the
+///   schemas are marked as nullable
+/// - the fields of structs are combined in the same way
+/// - integer types are widened, mixed integer and float types result in
+///   `Float64` marked with `Strategy::InconsistentTypes` to accept the
+///   integers in serialization. The strategies of numeric fields are kept
+/// - strings are widened to `LargeUtf8` or to dictionaries. Other types, e.g.,
+///   integers and dates, are not merged
+/// - the variants of unions are combined
+///
+/// Fields with conflicting types result in an error that includes the path of
+/// the field.
+///
+/// Example:
+///
+/// ```rust
+/// # use serde_arrow::_impl::arrow2 as arrow2;
+/// use arrow2::datatypes::{DataType, Field};
+/// use serde_arrow::arrow2::merge_fields;
+///
+/// let first = vec![
+///     Field::new("a", DataType::Int8, false),
+///     Field::new("b", DataType::Utf8, false),
+/// ];
+/// let second = vec![
+///     Field::new("a", DataType::UInt16, true),
+/// ];
+///
+/// let fields = merge_fields(&[first, second]).unwrap();
+/// let expected = vec![
+///     Field::new("a", DataType::Int32, true),
+///     Field::new("b", DataType::Utf8, true),
+/// ];
+/// assert_eq!(fields, expected);
+/// ```
+///
+pub fn merge_fields(schemas: &[Vec<Field>]) -> Result<Vec<Field>> {
+    let schemas = schemas
+        .iter()
+        .map(|fields| fields.iter().map(GenericField::try_from).collect())
+        .collect::<Result<Vec<Vec<GenericField>>>>()?;
+
+    internal::merge::merge_fields(&schemas)?
+        .iter()
+        .map(|f| f.try_into())
+        .collect()
+}
+
 /// Serialize a sequence of objects representing a single array into an array
 ///
 /// Example:
@@ -364,6 +541,47 @@ impl ArraysBuilder {
         Ok(Self(internal::GenericBuilder::new_for_arrays(&fields)?))
     }
 
+    /// Build a new ArraysBuilder for the given fields with custom options
+    ///
+    /// The options allow to serialize Rust types whose fields differ from the
+    /// schema, e.g., to ignore fields not present in the schema:
+    ///
+    /// ```rust
+    /// # use serde_arrow::_impl::arrow2 as arrow2;
+    /// use arrow2::datatypes::{DataType, Field};
+    /// use serde::Serialize;
+    /// use serde_arrow::{arrow2::ArraysBuilder, experimental::CompilationOptions};
+    ///
+    /// ##[derive(Serialize)]
+    /// struct Record {
+    ///     a: i64,
+    ///     removed: String,
+    /// }
+    ///
+    /// let fields = vec![
+    ///     Field::new("a", DataType::Int64, false),
+    ///     Field::new("added", DataType::Boolean, true),
+    /// ];
+    /// let options = CompilationOptions::default().ignore_unknown_fields(true);
+    ///
+    /// let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+    /// builder.push(&Record { a: 1, removed: String::from("foo") }).unwrap();
+    ///
+    /// let arrays = builder.build_arrays().unwrap();
+    /// assert_eq!(arrays.len(), 2);
+    /// assert_eq!(arrays[1].null_count(), 1);
+    /// ```
+    ///
+    pub fn new_with_options(fields: &[Field], options: CompilationOptions) -> Result<Self> {
+        let fields = fields
+            .iter()
+            .map(GenericField::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self(internal::GenericBuilder::new_for_arrays_with_options(
+            &fields, options,
+        )?))
+    }
+
     /// Add a single record to the arrays
     ///
     pub fn push<T: Serialize + ?Sized>(&mut self, item: &T) -> Result<()> {
@@ -376,6 +594,17 @@ impl ArraysBuilder {
         self.0.extend(items)
     }
 
+    /// The number of rows per field path filled with defaults so far
+    ///
+    /// Fields are only filled with defaults, if the builder was constructed
+    /// with
+    /// [`CompilationOptions::fill_missing_with_defaults`][crate::experimental::CompilationOptions::fill_missing_with_defaults].
+    /// The counts are reset when the arrays are built.
+    ///
+    pub fn defaulted_fields(&self) -> BTreeMap<String, usize> {
+        self.0 .0.defaulted_fields()
+    }
+
     /// Build the arrays from the rows pushed to far.
     ///
     /// This operation will reset the underlying buffers and start a new batch.
@@ -385,6 +614,79 @@ impl ArraysBuilder {
     }
 }
 
+/// Determine the schema incrementally from records
+///
+/// In contrast to [serialize_into_fields], the records can be traced
+/// individually or in batches. The fields can be determined at any point and
+/// tracing can continue afterwards, e.g., to determine the schema from the
+/// first records of a stream or to refine the schema while buffering.
+///
+/// If tracing a record fails, the tracer may be left in an inconsistent state
+/// and should not be used further.
+///
+/// Example:
+///
+/// ```rust
+/// # use serde_arrow::_impl::arrow2 as arrow2;
+/// use arrow2::datatypes::{DataType, Field};
+/// use serde::Serialize;
+/// use serde_arrow::arrow2::SchemaTracer;
+///
+/// ##[derive(Serialize)]
+/// struct Record {
+///     a: Option<f32>,
+///     b: u64,
+/// }
+///
+/// let mut tracer = SchemaTracer::new(Default::default());
+/// tracer.trace(&Record { a: None, b: 2 }).unwrap();
+///
+/// // the type of `a` is not yet known
+/// assert!(tracer.to_fields().is_err());
+///
+/// tracer.trace_batch(&[Record { a: Some(1.0), b: 3 }]).unwrap();
+///
+/// let fields = tracer.to_fields().unwrap();
+/// let expected = vec![
+///     Field::new("a", DataType::Float32, true),
+///     Field::new("b", DataType::UInt64, false),
+/// ];
+/// assert_eq!(fields, expected);
+/// ```
+pub struct SchemaTracer(internal::GenericSchemaTracer);
+
+impl std::fmt::Debug for SchemaTracer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SchemaTracer<...>")
+    }
+}
+
+impl SchemaTracer {
+    /// Build a new SchemaTracer with the given options
+    ///
+    pub fn new(options: TracingOptions) -> Self {
+        Self(internal::GenericSchemaTracer::new(options))
+    }
+
+    /// Trace a single record
+    ///
+    pub fn trace<T: Serialize + ?Sized>(&mut self, item: &T) -> Result<()> {
+        self.0.trace(item)
+    }
+
+    /// Trace multiple records, e.g., a vector of structs
+    ///
+    pub fn trace_batch<T: Serialize + ?Sized>(&mut self, items: &T) -> Result<()> {
+        self.0.trace_batch(items)
+    }
+
+    /// Determine the fields from the records traced so far
+    ///
+    pub fn to_fields(&mut self) -> Result<Vec<Field>> {
+        self.0.get_fields()?.iter().map(|f| f.try_into()).collect()
+    }
+}
+
 /// Experimental functionality that is not subject to semver compatibility
 pub mod experimental {
     pub use super::schema::find_field_mut;
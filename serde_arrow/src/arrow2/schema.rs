@@ -3,7 +3,7 @@ use crate::{
     arrow2::display,
     internal::{
         error::{error, fail, Error, Result},
-        schema::{GenericDataType, GenericField, Strategy, STRATEGY_KEY},
+        schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy, STRATEGY_KEY},
     },
 };
 
@@ -116,6 +116,7 @@ impl TryFrom<&Field> for GenericField {
             DataType::Float64 => GenericDataType::F64,
             DataType::Utf8 => GenericDataType::Utf8,
             DataType::LargeUtf8 => GenericDataType::LargeUtf8,
+            DataType::Date32 => GenericDataType::Date32,
             DataType::Date64 => GenericDataType::Date64,
             DataType::List(field) => {
                 children.push(GenericField::try_from(field.as_ref())?);
@@ -197,6 +198,7 @@ impl TryFrom<&GenericField> for Field {
             GenericDataType::F16 => DataType::Float16,
             GenericDataType::F32 => DataType::Float32,
             GenericDataType::F64 => DataType::Float64,
+            GenericDataType::Date32 => DataType::Date32,
             GenericDataType::Date64 => DataType::Date64,
             GenericDataType::Utf8 => DataType::Utf8,
             GenericDataType::LargeUtf8 => DataType::LargeUtf8,
@@ -273,3 +275,35 @@ impl TryFrom<&GenericField> for Field {
         Ok(field)
     }
 }
+
+impl SerdeArrowSchema {
+    /// Build a schema from `arrow2` fields
+    pub fn from_arrow2_fields(fields: &[Field]) -> Result<Self> {
+        Self::try_from(fields)
+    }
+
+    /// Convert the schema into `arrow2` fields
+    pub fn to_arrow2_fields(&self) -> Result<Vec<Field>> {
+        Vec::<Field>::try_from(self)
+    }
+}
+
+impl TryFrom<&[Field]> for SerdeArrowSchema {
+    type Error = Error;
+
+    fn try_from(fields: &[Field]) -> Result<Self> {
+        let fields = fields
+            .iter()
+            .map(GenericField::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { fields })
+    }
+}
+
+impl TryFrom<&SerdeArrowSchema> for Vec<Field> {
+    type Error = Error;
+
+    fn try_from(schema: &SerdeArrowSchema) -> Result<Self> {
+        schema.fields.iter().map(Field::try_from).collect()
+    }
+}
@@ -124,6 +124,9 @@ fn build_array(buffers: &mut Buffers, mapping: &ArrayMapping) -> Result<Box<dyn
         M::F64 {
             buffer, validity, ..
         } => build_array_primitive!(buffers, f64, u64, Float64, *buffer, *validity),
+        M::Date32 {
+            buffer, validity, ..
+        } => build_array_primitive!(buffers, i32, u32, Date32, *buffer, *validity),
         M::Date64 {
             buffer, validity, ..
         } => build_array_primitive!(buffers, i64, u64, Date64, *buffer, *validity),
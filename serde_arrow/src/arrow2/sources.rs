@@ -13,8 +13,8 @@ use crate::{
         error::{error, fail, Result},
         event::Event,
         generic_sources::{
-            ListSource, MapSource, NaiveDateTimeStrSource, StructSource, TupleSource, UnionSource,
-            UtcDateTimeStrSource,
+            ListSource, MapSource, NaiveDateStrSource, NaiveDateTimeStrSource, StructSource,
+            TupleSource, UnionSource, UtcDateTimeStrSource,
         },
         schema::{GenericField, Strategy, STRATEGY_KEY},
         source::{AddOuterSequenceSource, DynamicSource, EventSource},
@@ -84,6 +84,19 @@ pub fn build_dynamic_source<'a>(
                 .downcast_ref::<Utf8Array<i64>>()
                 .ok_or_else(|| error!("mismatched types"))?,
         )),
+        DataType::Date32 => {
+            if let Some(strategy) = field.metadata.get(STRATEGY_KEY) {
+                let strategy: Strategy = strategy.parse()?;
+                match strategy {
+                    Strategy::NaiveStrAsDate32 => DynamicSource::new(NaiveDateStrSource(
+                        PrimitiveEventSource::<i32>::from_array(array)?,
+                    )),
+                    s => fail!("Invalid strategy {s} for Date32 column"),
+                }
+            } else {
+                build_dynamic_primitive_source::<i32>(field, array)?
+            }
+        }
         DataType::Date64 => {
             if let Some(strategy) = field.metadata.get(STRATEGY_KEY) {
                 let strategy: Strategy = strategy.parse()?;
@@ -123,7 +136,17 @@ pub fn build_dynamic_source<'a>(
             if field_indices.is_some() {
                 fail!("Explicit field indices are currently not supported for unions");
             }
-            build_dynamic_union_source(fields, array)?
+            let strategy: Option<Strategy> =
+                if let Some(strategy) = field.metadata.get(STRATEGY_KEY) {
+                    Some(strategy.parse()?)
+                } else {
+                    None
+                };
+            match strategy {
+                Some(Strategy::UntaggedUnion) => build_dynamic_union_source(fields, array, true)?,
+                None => build_dynamic_union_source(fields, array, false)?,
+                Some(strategy) => fail!("Invalid strategy {strategy} for Union column"),
+            }
         }
         DataType::Map(field, _) => {
             let kv_fields = match field.data_type() {
@@ -254,6 +277,7 @@ pub fn build_dynamic_list_source<'a, T: Offset>(
 pub fn build_dynamic_union_source<'a>(
     fields: &'a [Field],
     array: &'a dyn Array,
+    untagged: bool,
 ) -> Result<DynamicSource<'a>> {
     let array = array.as_any().downcast_ref::<UnionArray>().ok_or_else(|| {
         error!(
@@ -277,7 +301,7 @@ pub fn build_dynamic_union_source<'a>(
 
     // TODO: test that the offsets are dense
 
-    let source = UnionSource::new(names, sources, types);
+    let source = UnionSource::new(names, sources, types, untagged);
     Ok(DynamicSource::new(source))
 }
 
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    arrow2::{deserialize_from_arrays, serialize_into_arrays, serialize_into_fields},
+    schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy, TracingOptions},
+};
+
+// NOTE: the types are identical to the generated code below
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub id: u64,
+    #[serde(rename = "userName")]
+    pub user_name: Option<String>,
+    pub tags: Vec<String>,
+    pub attrs: std::collections::HashMap<String, f64>,
+    pub position: (i32, i32),
+    pub created: chrono::NaiveDateTime,
+    pub nested: Option<Nested>,
+    pub event: Event,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Event {
+    Start,
+    Value(i64),
+    Point(EventPoint),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventPoint {
+    pub x: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Nested {
+    pub r#type: String,
+}
+
+const EXPECTED: &str = r#"use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub id: u64,
+    #[serde(rename = "userName")]
+    pub user_name: Option<String>,
+    pub tags: Vec<String>,
+    pub attrs: std::collections::HashMap<String, f64>,
+    pub position: (i32, i32),
+    pub created: chrono::NaiveDateTime,
+    pub nested: Option<Nested>,
+    pub event: Event,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Event {
+    Start,
+    Value(i64),
+    Point(EventPoint),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventPoint {
+    pub x: f32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Nested {
+    pub r#type: String,
+}
+"#;
+
+fn example() -> Vec<Record> {
+    vec![
+        Record {
+            id: 0,
+            user_name: Some(String::from("foo")),
+            tags: vec![String::from("a")],
+            attrs: HashMap::from([(String::from("x"), 1.0)]),
+            position: (1, 2),
+            created: chrono::NaiveDateTime::default(),
+            nested: Some(Nested {
+                r#type: String::from("bar"),
+            }),
+            event: Event::Start,
+        },
+        Record {
+            id: 1,
+            user_name: None,
+            tags: vec![],
+            attrs: HashMap::new(),
+            position: (3, 4),
+            created: chrono::NaiveDateTime::default(),
+            nested: None,
+            event: Event::Value(5),
+        },
+        Record {
+            id: 2,
+            user_name: None,
+            tags: vec![],
+            attrs: HashMap::new(),
+            position: (5, 6),
+            created: chrono::NaiveDateTime::default(),
+            nested: None,
+            event: Event::Point(EventPoint { x: 7.0 }),
+        },
+    ]
+}
+
+#[test]
+fn generated_code_round_trips() {
+    let items = example();
+    let options = TracingOptions::default()
+        .allow_null_fields(true)
+        .map_as_struct(false)
+        .guess_dates(true);
+    let fields = serialize_into_fields(&items, options).unwrap();
+
+    let schema = SerdeArrowSchema::from_arrow2_fields(&fields).unwrap();
+    assert_eq!(schema.to_rust_code("Record").unwrap(), EXPECTED);
+
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    let actual: Vec<Record> = deserialize_from_arrays(&fields, &arrays).unwrap();
+    assert_eq!(actual, items);
+}
+
+#[test]
+fn identifiers() {
+    let schema = SerdeArrowSchema::new()
+        .with_field(GenericField::new("CamelCase", GenericDataType::Bool, false))
+        .with_field(GenericField::new("with space", GenericDataType::I8, false))
+        .with_field(GenericField::new("0", GenericDataType::U8, false))
+        .with_field(GenericField::new("match", GenericDataType::F64, true));
+
+    let expected = r#"use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Row {
+    #[serde(rename = "CamelCase")]
+    pub camel_case: bool,
+    #[serde(rename = "with space")]
+    pub with_space: i8,
+    #[serde(rename = "0")]
+    pub field_0: u8,
+    pub r#match: Option<f64>,
+}
+"#;
+    assert_eq!(schema.to_rust_code("row").unwrap(), expected);
+}
+
+#[test]
+fn dates_dictionaries_and_untagged_unions() {
+    let schema = SerdeArrowSchema::new()
+        .with_field(
+            GenericField::new("utc", GenericDataType::Date64, false)
+                .with_strategy(Strategy::UtcStrAsDate64),
+        )
+        .with_field(
+            GenericField::new("day", GenericDataType::Date32, false)
+                .with_strategy(Strategy::NaiveStrAsDate32),
+        )
+        .with_field(GenericField::new("millis", GenericDataType::Date64, false))
+        .with_field(
+            GenericField::new("label", GenericDataType::Dictionary, false)
+                .with_child(GenericField::new("key", GenericDataType::U32, false))
+                .with_child(GenericField::new(
+                    "value",
+                    GenericDataType::LargeUtf8,
+                    false,
+                )),
+        )
+        .with_field(
+            GenericField::new("value", GenericDataType::Union, false)
+                .with_strategy(Strategy::UntaggedUnion)
+                .with_child(GenericField::new("0", GenericDataType::I64, false))
+                .with_child(GenericField::new("1", GenericDataType::LargeUtf8, false)),
+        );
+
+    let expected = r#"use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub utc: chrono::DateTime<chrono::Utc>,
+    pub day: chrono::NaiveDate,
+    pub millis: i64,
+    pub label: String,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Value {
+    Variant0(i64),
+    Variant1(String),
+}
+"#;
+    assert_eq!(schema.to_rust_code("Record").unwrap(), expected);
+}
+
+#[test]
+fn type_name_collisions() {
+    let nested = GenericField::new("record", GenericDataType::Struct, false)
+        .with_child(GenericField::new("a", GenericDataType::U8, false));
+    let schema = SerdeArrowSchema::new().with_field(nested);
+
+    let code = schema.to_rust_code("Record").unwrap();
+    assert!(code.contains("pub struct Record {"));
+    assert!(code.contains("pub record: Record2,"));
+    assert!(code.contains("pub struct Record2 {"));
+}
+
+#[test]
+fn identifier_collisions() {
+    let schema = SerdeArrowSchema::new()
+        .with_field(GenericField::new("a-b", GenericDataType::Bool, false))
+        .with_field(GenericField::new("a_b", GenericDataType::I8, false))
+        .with_field(GenericField::new("a b", GenericDataType::U8, false))
+        .with_field(GenericField::new("type", GenericDataType::U16, false))
+        .with_field(GenericField::new("Type", GenericDataType::U32, false))
+        .with_field(
+            GenericField::new("value", GenericDataType::Union, false)
+                .with_child(GenericField::new("a-b", GenericDataType::Null, true))
+                .with_child(GenericField::new("a_b", GenericDataType::Null, true)),
+        );
+
+    let expected = r#"use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(rename = "a-b")]
+    pub a_b: bool,
+    #[serde(rename = "a_b")]
+    pub a_b_2: i8,
+    #[serde(rename = "a b")]
+    pub a_b_3: u8,
+    pub r#type: u16,
+    #[serde(rename = "Type")]
+    pub type_2: u32,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    #[serde(rename = "a-b")]
+    AB,
+    #[serde(rename = "a_b")]
+    AB2,
+}
+"#;
+    assert_eq!(schema.to_rust_code("Record").unwrap(), expected);
+}
+
+#[test]
+fn keywords_and_reserved_type_names() {
+    let schema = SerdeArrowSchema::new()
+        .with_field(GenericField::new("self", GenericDataType::Bool, false))
+        .with_field(GenericField::new("super", GenericDataType::I8, false))
+        .with_field(GenericField::new("crate", GenericDataType::U8, false))
+        .with_field(GenericField::new("_", GenericDataType::U16, false))
+        .with_field(
+            GenericField::new("string", GenericDataType::Struct, false).with_child(
+                GenericField::new("self", GenericDataType::Struct, false)
+                    .with_child(GenericField::new("a", GenericDataType::U8, false)),
+            ),
+        )
+        .with_field(
+            GenericField::new("value", GenericDataType::Union, false)
+                .with_child(GenericField::new("self", GenericDataType::Null, true)),
+        );
+
+    let expected = r#"use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    #[serde(rename = "self")]
+    pub self_: bool,
+    #[serde(rename = "super")]
+    pub super_: i8,
+    #[serde(rename = "crate")]
+    pub crate_: u8,
+    #[serde(rename = "_")]
+    pub field_: u16,
+    pub string: String2,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    #[serde(rename = "self")]
+    Self2,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct String2 {
+    #[serde(rename = "self")]
+    pub self_: Self2,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Self2 {
+    pub a: u8,
+}
+"#;
+    assert_eq!(schema.to_rust_code("Record").unwrap(), expected);
+}
+
+#[test]
+fn invalid_schemas_are_rejected() {
+    let schema =
+        SerdeArrowSchema::new().with_field(GenericField::new("a", GenericDataType::Map, false));
+    assert!(schema.to_rust_code("Record").is_err());
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    _impl::arrow2::datatypes::{DataType, Field, UnionMode},
+    schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy, TracingOptions},
+};
+
+#[derive(Serialize)]
+struct Record {
+    id: u32,
+    name: Option<String>,
+    scores: Vec<f32>,
+    position: (i8, i8),
+    attrs: HashMap<String, i64>,
+    created: String,
+    event: Event,
+    value: Value,
+}
+
+#[derive(Serialize)]
+enum Event {
+    Start,
+    Point { x: f64 },
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Value {
+    Int(u16),
+    Str(String),
+}
+
+#[test]
+fn round_trip() {
+    let items = [
+        Record {
+            id: 0,
+            name: Some(String::from("foo")),
+            scores: vec![1.0],
+            position: (1, 2),
+            attrs: HashMap::from([(String::from("a"), 1)]),
+            created: String::from("2023-01-31T12:00:00Z"),
+            event: Event::Start,
+            value: Value::Int(3),
+        },
+        Record {
+            id: 1,
+            name: None,
+            scores: vec![],
+            position: (3, 4),
+            attrs: HashMap::new(),
+            created: String::from("2023-01-31T13:00:00Z"),
+            event: Event::Point { x: 5.0 },
+            value: Value::Str(String::from("bar")),
+        },
+    ];
+    let options = TracingOptions::default()
+        .allow_null_fields(true)
+        .map_as_struct(false)
+        .guess_dates(true)
+        .untagged_unions(true);
+    let schema = SerdeArrowSchema::from_samples(&items, options).unwrap();
+
+    let json_schema = schema.to_json_schema().unwrap();
+    let mut actual = SerdeArrowSchema::from_json_schema(&json_schema).unwrap();
+
+    // NOTE: the order of properties is not preserved by serde_json
+    let mut expected = schema;
+    expected.fields.sort_by(|a, b| a.name.cmp(&b.name));
+    actual.fields.sort_by(|a, b| a.name.cmp(&b.name));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn export() {
+    let schema =
+        SerdeArrowSchema::new()
+            .with_field(GenericField::new("a", GenericDataType::I32, false))
+            .with_field(GenericField::new("b", GenericDataType::LargeUtf8, true))
+            .with_field(
+                GenericField::new("c", GenericDataType::Date64, false)
+                    .with_strategy(Strategy::UtcStrAsDate64),
+            )
+            .with_field(
+                GenericField::new("d", GenericDataType::LargeList, true)
+                    .with_child(GenericField::new("element", GenericDataType::Bool, false)),
+            );
+
+    let expected = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "type": "object",
+        "properties": {
+            "a": {"type": "integer", "format": "int32"},
+            "b": {"type": ["string", "null"]},
+            "c": {"type": "string", "format": "date-time"},
+            "d": {"type": ["array", "null"], "items": {"type": "boolean"}},
+        },
+        "required": ["a", "c"],
+    });
+    assert_eq!(schema.to_json_schema().unwrap(), expected);
+}
+
+#[test]
+fn import() {
+    let json_schema = json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "created": {"type": "string", "format": "date-time"},
+            "day": {"type": "string", "format": "date"},
+            "note": {"type": "string"},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "location": {"$ref": "#/$defs/Location"},
+            "kind": {
+                "oneOf": [
+                    {"const": "Unknown"},
+                    {
+                        "type": "object",
+                        "properties": {"Known": {"type": "string"}},
+                        "required": ["Known"],
+                    },
+                ],
+            },
+            "amount": {"oneOf": [{"type": "number"}, {"type": "null"}]},
+        },
+        "required": ["id", "created", "day", "tags", "location", "kind"],
+        "$defs": {
+            "Location": {
+                "type": "object",
+                "properties": {"lat": {"type": "number"}, "lon": {"type": "number"}},
+                "required": ["lat", "lon"],
+            },
+        },
+    });
+
+    let schema = SerdeArrowSchema::from_json_schema(&json_schema).unwrap();
+    let mut fields = schema.to_arrow2_fields().unwrap();
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let kind = DataType::Union(
+        vec![
+            Field::new("Unknown", DataType::Null, true),
+            Field::new("Known", DataType::LargeUtf8, false),
+        ],
+        None,
+        UnionMode::Dense,
+    );
+    let location = DataType::Struct(vec![
+        Field::new("lat", DataType::Float64, false),
+        Field::new("lon", DataType::Float64, false),
+    ]);
+    let expected = vec![
+        Field::new("amount", DataType::Float64, true),
+        Field::new("created", DataType::Date64, false)
+            .with_metadata(Strategy::UtcStrAsDate64.into()),
+        Field::new("day", DataType::Date32, false).with_metadata(Strategy::NaiveStrAsDate32.into()),
+        Field::new("id", DataType::Int64, false),
+        Field::new("kind", kind, false),
+        Field::new("location", location, false),
+        Field::new("note", DataType::LargeUtf8, true),
+        Field::new(
+            "tags",
+            DataType::LargeList(Box::new(Field::new("element", DataType::LargeUtf8, false))),
+            false,
+        ),
+    ];
+    assert_eq!(fields, expected);
+}
+
+#[test]
+fn import_errors() {
+    let not_an_object = json!({"type": "array", "items": {"type": "integer"}});
+    assert!(SerdeArrowSchema::from_json_schema(&not_an_object).is_err());
+
+    let unknown_ref = json!({
+        "type": "object",
+        "properties": {"a": {"$ref": "#/$defs/Missing"}},
+    });
+    let err = SerdeArrowSchema::from_json_schema(&unknown_ref).unwrap_err();
+    assert!(err.to_string().contains("$.a"), "{err}");
+
+    let recursive = json!({
+        "type": "object",
+        "properties": {"node": {"$ref": "#/$defs/Node"}},
+        "$defs": {
+            "Node": {
+                "type": "object",
+                "properties": {"child": {"$ref": "#/$defs/Node"}},
+            },
+        },
+    });
+    let err = SerdeArrowSchema::from_json_schema(&recursive).unwrap_err();
+    assert!(err.to_string().contains("recursive"), "{err}");
+
+    let unsupported_item = json!({
+        "type": "object",
+        "properties": {"a": {"type": "array", "items": {"type": "unknown"}}},
+    });
+    let err = SerdeArrowSchema::from_json_schema(&unsupported_item).unwrap_err();
+    assert!(err.to_string().contains("$.a.element"), "{err}");
+
+    let nullable_union = json!({
+        "type": "object",
+        "properties": {
+            "kind": {
+                "oneOf": [
+                    {"const": "Unknown"},
+                    {
+                        "type": "object",
+                        "properties": {"Known": {"type": "string"}},
+                        "required": ["Known"],
+                    },
+                    {"type": "null"},
+                ],
+            },
+        },
+    });
+    let err = SerdeArrowSchema::from_json_schema(&nullable_union).unwrap_err();
+    assert!(err.to_string().contains("$.kind"), "{err}");
+    assert!(err.to_string().contains("cannot be nullable"), "{err}");
+}
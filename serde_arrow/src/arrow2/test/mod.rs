@@ -1,9 +1,13 @@
+mod codegen;
 mod generic_sources;
 mod implementation_docs;
+#[cfg(feature = "json")]
+mod json_schema;
 mod round_trip;
 mod round_trip_array;
 mod schema;
 mod schema_mod;
+mod serialization_options;
 mod sources;
 pub(crate) mod utils;
 
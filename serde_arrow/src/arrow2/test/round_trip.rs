@@ -566,3 +566,169 @@ fn test_maps_with_missing_items() {
 
     assert_eq!(actual, expected);
 }
+
+/// Test that struct arrays can be deserialized into maps, null fields are only
+/// skipped for maps
+#[test]
+fn test_structs_as_maps() {
+    #[derive(Debug, Serialize)]
+    struct Record {
+        a: i32,
+        b: Option<i32>,
+    }
+
+    let items = vec![Record { a: 0, b: Some(1) }, Record { a: 2, b: None }];
+
+    let fields = serialize_into_fields(&items, Default::default()).unwrap();
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    let actual: Vec<HashMap<String, i32>> = deserialize_from_arrays(&fields, &arrays).unwrap();
+
+    let mut expected: Vec<HashMap<String, i32>> = Vec::new();
+    let mut item = HashMap::new();
+    item.insert(String::from("a"), 0);
+    item.insert(String::from("b"), 1);
+    expected.push(item);
+
+    let mut item = HashMap::new();
+    item.insert(String::from("a"), 2);
+    expected.push(item);
+
+    assert_eq!(actual, expected);
+
+    let actual: Vec<serde_json::Value> = deserialize_from_arrays(&fields, &arrays).unwrap();
+    let expected = vec![
+        serde_json::json!({"a": 0, "b": 1}),
+        serde_json::json!({"a": 2, "b": null}),
+    ];
+    assert_eq!(actual, expected);
+}
+
+/// Test that records of internally tagged enums with missing fields round trip
+#[test]
+fn test_internally_tagged_records() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    enum Record {
+        A { a: i32 },
+        B { b: String },
+        C,
+    }
+
+    let items = vec![
+        Record::A { a: 0 },
+        Record::B {
+            b: String::from("b"),
+        },
+        Record::C,
+    ];
+
+    let fields = serialize_into_fields(&items, Default::default()).unwrap();
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    let round_tripped: Vec<Record> = deserialize_from_arrays(&fields, &arrays).unwrap();
+
+    assert_eq!(round_tripped, items);
+}
+
+/// Test that null fields are skipped also for maps with optional values, while
+/// optional fields of flattened structs are still deserialized as `None`
+#[test]
+fn test_structs_as_maps_null_fields() {
+    #[derive(Debug, Serialize)]
+    struct Record {
+        a: i32,
+        b: Option<i32>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Flattened {
+        a: i32,
+        #[serde(flatten)]
+        inner: Inner,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Inner {
+        b: Option<i32>,
+    }
+
+    let items = vec![Record { a: 0, b: Some(1) }, Record { a: 2, b: None }];
+
+    let fields = serialize_into_fields(&items, Default::default()).unwrap();
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    let actual: Vec<HashMap<String, Option<i32>>> =
+        deserialize_from_arrays(&fields, &arrays).unwrap();
+
+    let mut expected: Vec<HashMap<String, Option<i32>>> = Vec::new();
+    let mut item = HashMap::new();
+    item.insert(String::from("a"), Some(0));
+    item.insert(String::from("b"), Some(1));
+    expected.push(item);
+
+    let mut item = HashMap::new();
+    item.insert(String::from("a"), Some(2));
+    expected.push(item);
+
+    assert_eq!(actual, expected);
+
+    let actual: Vec<Flattened> = deserialize_from_arrays(&fields, &arrays).unwrap();
+    let expected = vec![
+        Flattened {
+            a: 0,
+            inner: Inner { b: Some(1) },
+        },
+        Flattened {
+            a: 2,
+            inner: Inner { b: None },
+        },
+    ];
+    assert_eq!(actual, expected);
+}
+
+/// Test that null fields are kept in `deserialize_any`, e.g., for unit fields of
+/// internally tagged enums
+#[test]
+fn test_structs_with_unit_fields_via_deserialize_any() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(tag = "type")]
+    enum Record {
+        A { a: i32, unit: () },
+    }
+
+    let items = vec![Record::A { a: 0, unit: () }, Record::A { a: 1, unit: () }];
+
+    let fields =
+        serialize_into_fields(&items, TracingOptions::default().allow_null_fields(true)).unwrap();
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    let actual: Vec<Record> = deserialize_from_arrays(&fields, &arrays).unwrap();
+    assert_eq!(actual, items);
+}
+
+/// Test that structs with more than 128 fields round trip
+#[test]
+fn test_structs_with_many_fields() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        nested: HashMap<String, u32>,
+    }
+
+    let fields = (0..200)
+        .map(|idx| Field::new(format!("f{idx}"), DataType::UInt32, false))
+        .collect::<Vec<_>>();
+    let nested = Field::new("nested", DataType::Struct(fields), false)
+        .with_metadata(Strategy::MapAsStruct.into());
+
+    let item = (0..200)
+        .map(|idx| (format!("f{idx}"), idx))
+        .collect::<HashMap<_, _>>();
+
+    let items = vec![
+        Record {
+            nested: item.clone(),
+        },
+        Record { nested: item },
+    ];
+    let fields = vec![nested];
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    let actual: Vec<Record> = deserialize_from_arrays(&fields, &arrays).unwrap();
+    assert_eq!(actual, items);
+}
@@ -271,3 +271,1116 @@ fn inconsistent_types() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn coerce_json_numbers() {
+    let items = json!([
+        {"value": 1},
+        {"value": -2},
+        {"value": 0.5},
+    ]);
+
+    let actual =
+        serialize_into_fields(&items, TracingOptions::default().coerce_numbers(true)).unwrap();
+    let expected = vec![Field::new("value", DataType::Float64, false)
+        .with_metadata(Strategy::InconsistentTypes.into())];
+
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn coerce_json_integers() {
+    let items = json!([{"value": 5}, {"value": -3}]);
+
+    let fields =
+        serialize_into_fields(&items, TracingOptions::default().coerce_numbers(true)).unwrap();
+    let expected = vec![Field::new("value", DataType::Int64, false)
+        .with_metadata(Strategy::InconsistentTypes.into())];
+    assert_eq!(fields, expected);
+
+    let items = json!([{"value": u64::MAX}, {"value": -3}]);
+
+    let fields =
+        serialize_into_fields(&items, TracingOptions::default().coerce_numbers(true)).unwrap();
+    let expected = vec![Field::new("value", DataType::Float64, false)
+        .with_metadata(Strategy::InconsistentTypes.into())];
+    assert_eq!(fields, expected);
+}
+
+mod from_type {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        _impl::arrow2::datatypes::{DataType, Field, UnionMode},
+        arrow2::{
+            serialize_into_field_from_type, serialize_into_fields, serialize_into_fields_from_type,
+        },
+        internal::schema::TracingOptions,
+        schema::Strategy,
+    };
+
+    #[test]
+    fn nested_types() {
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Record {
+            a: Option<i32>,
+            b: Vec<String>,
+            c: Inner,
+            d: (u8, f64),
+            e: HashMap<String, bool>,
+        }
+
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Inner {
+            value: char,
+        }
+
+        let actual = serialize_into_fields_from_type::<Record>(Default::default()).unwrap();
+        let expected = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new(
+                "b",
+                DataType::LargeList(Box::new(Field::new("element", DataType::LargeUtf8, false))),
+                false,
+            ),
+            Field::new(
+                "c",
+                DataType::Struct(vec![Field::new("value", DataType::UInt32, false)]),
+                false,
+            ),
+            Field::new(
+                "d",
+                DataType::Struct(vec![
+                    Field::new("0", DataType::UInt8, false),
+                    Field::new("1", DataType::Float64, false),
+                ]),
+                false,
+            )
+            .with_metadata(Strategy::TupleAsStruct.into()),
+            Field::new(
+                "e",
+                DataType::Map(
+                    Box::new(Field::new(
+                        "entries",
+                        DataType::Struct(vec![
+                            Field::new("key", DataType::LargeUtf8, false),
+                            Field::new("value", DataType::Boolean, false),
+                        ]),
+                        false,
+                    )),
+                    false,
+                ),
+                false,
+            ),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn all_enum_variants() {
+        #[derive(Serialize, Deserialize)]
+        enum Outer {
+            A(Inner),
+            B { value: u16 },
+            C(i64, i64),
+        }
+
+        #[derive(Serialize, Deserialize)]
+        enum Inner {
+            X(u8),
+            Y(bool),
+            Z(f32),
+        }
+
+        let actual = serialize_into_field_from_type::<Outer>("root", Default::default()).unwrap();
+
+        let items = [
+            Outer::A(Inner::X(0)),
+            Outer::A(Inner::Y(false)),
+            Outer::A(Inner::Z(0.0)),
+            Outer::B { value: 0 },
+            Outer::C(0, 0),
+        ];
+        let expected =
+            crate::arrow2::serialize_into_field(&items, "root", Default::default()).unwrap();
+
+        assert_eq!(actual, expected);
+        assert!(
+            matches!(actual.data_type, DataType::Union(ref fields, None, UnionMode::Dense) if fields.len() == 3)
+        );
+    }
+
+    #[test]
+    fn same_as_traced_from_samples() {
+        #[derive(Serialize, Deserialize)]
+        struct Record {
+            id: u64,
+            name: String,
+            tags: Vec<Tag>,
+            score: Option<f32>,
+        }
+
+        #[derive(Serialize, Deserialize)]
+        enum Tag {
+            Label(String),
+            Priority(u8),
+        }
+
+        let items = [Record {
+            id: 0,
+            name: String::new(),
+            tags: vec![Tag::Label(String::new()), Tag::Priority(0)],
+            score: Some(0.0),
+        }];
+
+        let actual = serialize_into_fields_from_type::<Record>(Default::default()).unwrap();
+        let expected = serialize_into_fields(&items, Default::default()).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn recursive_types_are_rejected() {
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Node {
+            value: i32,
+            children: Vec<Node>,
+        }
+
+        let err = serialize_into_fields_from_type::<Node>(Default::default()).unwrap_err();
+        assert!(err.to_string().contains("recursive type Node"), "{err}");
+    }
+
+    #[test]
+    fn same_named_types_are_not_recursive() {
+        mod inner {
+            #[allow(dead_code)]
+            #[derive(serde::Deserialize)]
+            pub struct Node {
+                pub value: i32,
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Node {
+            child: inner::Node,
+        }
+
+        let actual = serialize_into_fields_from_type::<Node>(Default::default()).unwrap();
+        let expected = vec![Field::new(
+            "child",
+            DataType::Struct(vec![Field::new("value", DataType::Int32, false)]),
+            false,
+        )];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn chrono_types() {
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Record {
+            date: chrono::NaiveDate,
+            time: chrono::NaiveTime,
+            naive: chrono::NaiveDateTime,
+            utc: chrono::DateTime<chrono::Utc>,
+        }
+
+        let actual = serialize_into_fields_from_type::<Record>(Default::default()).unwrap();
+        let expected = vec![
+            Field::new("date", DataType::LargeUtf8, false),
+            Field::new("time", DataType::LargeUtf8, false),
+            Field::new("naive", DataType::LargeUtf8, false),
+            Field::new("utc", DataType::LargeUtf8, false),
+        ];
+        assert_eq!(actual, expected);
+
+        let actual =
+            serialize_into_fields_from_type::<Record>(TracingOptions::default().guess_dates(true))
+                .unwrap();
+        assert_eq!(actual[0].data_type, DataType::Date32);
+        assert_eq!(actual[2].data_type, DataType::Date64);
+        assert_eq!(actual[3].data_type, DataType::Date64);
+    }
+
+    #[test]
+    fn unparsable_sample_strings_give_clear_errors() {
+        #[allow(dead_code)]
+        struct Positive(u32);
+
+        struct PositiveVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for PositiveVisitor {
+            type Value = Positive;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(formatter, "a positive integer as a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Positive, E> {
+                v.parse().map(Positive).map_err(E::custom)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Positive {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(PositiveVisitor)
+            }
+        }
+
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Record {
+            value: Positive,
+        }
+
+        let err = serialize_into_fields_from_type::<Record>(Default::default()).unwrap_err();
+        assert!(err.to_string().contains("$.value"), "{err}");
+        assert!(err.to_string().contains("from a sample string"), "{err}");
+    }
+
+    #[test]
+    fn self_describing_types_are_rejected() {
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct Record {
+            value: serde_json::Value,
+        }
+
+        let err = serialize_into_fields_from_type::<Record>(TracingOptions::default()).unwrap_err();
+        assert!(err.to_string().contains("$.value"), "{err}");
+    }
+}
+
+mod schema_tracer {
+    use serde::Serialize;
+
+    use crate::{
+        _impl::arrow2::datatypes::{DataType, Field},
+        arrow2::{serialize_into_fields, SchemaTracer},
+        schema::TracingOptions,
+    };
+
+    #[derive(Serialize)]
+    struct Record {
+        a: Option<i32>,
+        b: u8,
+    }
+
+    #[test]
+    fn schema_is_refined_over_batches() {
+        let mut tracer = SchemaTracer::new(TracingOptions::default());
+
+        tracer.trace(&Record { a: None, b: 1 }).unwrap();
+        assert!(tracer.to_fields().is_err());
+
+        tracer
+            .trace_batch(&[Record { a: None, b: 2 }, Record { a: Some(3), b: 4 }])
+            .unwrap();
+
+        let actual = tracer.to_fields().unwrap();
+        let expected = vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::UInt8, false),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn same_as_serialize_into_fields() {
+        let items = vec![
+            Record { a: Some(1), b: 2 },
+            Record { a: None, b: 3 },
+            Record { a: Some(4), b: 5 },
+        ];
+
+        let mut tracer = SchemaTracer::new(TracingOptions::default());
+        for item in &items {
+            tracer.trace(item).unwrap();
+        }
+
+        let expected = serialize_into_fields(&items, TracingOptions::default()).unwrap();
+        assert_eq!(tracer.to_fields().unwrap(), expected);
+    }
+
+    #[test]
+    fn no_records() {
+        let mut tracer = SchemaTracer::new(TracingOptions::default());
+        assert!(tracer.to_fields().is_err());
+
+        tracer.trace(&Record { a: Some(1), b: 2 }).unwrap();
+        assert!(tracer.to_fields().is_ok());
+    }
+}
+
+mod merge {
+    use serde::Serialize;
+
+    use crate::{
+        _impl::arrow2::datatypes::{DataType, Field},
+        arrow2::{merge_fields, serialize_into_arrays, serialize_into_fields},
+        schema::{Strategy, TracingOptions},
+    };
+
+    #[test]
+    fn nullability_and_missing_fields() {
+        #[derive(Serialize)]
+        struct First {
+            a: u8,
+            b: Option<i32>,
+            nested: Nested,
+        }
+
+        #[derive(Serialize)]
+        struct Second {
+            a: Option<u8>,
+            c: bool,
+            nested: OtherNested,
+        }
+
+        #[derive(Serialize)]
+        struct Nested {
+            x: u8,
+        }
+
+        #[derive(Serialize)]
+        struct OtherNested {
+            x: u8,
+            y: f32,
+        }
+
+        let first = serialize_into_fields(
+            &[First {
+                a: 1,
+                b: Some(2),
+                nested: Nested { x: 3 },
+            }],
+            TracingOptions::default(),
+        )
+        .unwrap();
+        let second = serialize_into_fields(
+            &[Second {
+                a: None,
+                c: true,
+                nested: OtherNested { x: 4, y: 5.0 },
+            }],
+            TracingOptions::default().allow_null_fields(true),
+        )
+        .unwrap();
+
+        let actual = merge_fields(&[first, second]).unwrap();
+        let expected = vec![
+            Field::new("a", DataType::UInt8, true),
+            Field::new("b", DataType::Int32, true),
+            Field::new(
+                "nested",
+                DataType::Struct(vec![
+                    Field::new("x", DataType::UInt8, false),
+                    Field::new("y", DataType::Float32, true),
+                ]),
+                false,
+            ),
+            Field::new("c", DataType::Boolean, true),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn numbers_are_widened() {
+        let schemas = vec![
+            vec![
+                Field::new("a", DataType::Int8, false),
+                Field::new("b", DataType::UInt32, false),
+                Field::new("c", DataType::Float32, false),
+            ],
+            vec![
+                Field::new("a", DataType::Int64, false),
+                Field::new("b", DataType::UInt64, false),
+                Field::new("c", DataType::Float64, false),
+            ],
+            vec![
+                Field::new("a", DataType::UInt8, false),
+                Field::new("b", DataType::Float32, false),
+                Field::new("c", DataType::Int16, false),
+            ],
+        ];
+
+        let actual = merge_fields(&schemas).unwrap();
+        let expected = vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Float64, false)
+                .with_metadata(Strategy::InconsistentTypes.into()),
+            Field::new("c", DataType::Float64, false)
+                .with_metadata(Strategy::InconsistentTypes.into()),
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn merged_numbers_accept_the_original_values() {
+        #[derive(Serialize)]
+        struct First {
+            a: i32,
+        }
+
+        #[derive(Serialize)]
+        struct Second {
+            a: f32,
+        }
+
+        let first = [First { a: 1 }];
+        let second = [Second { a: 2.5 }];
+
+        let fields = merge_fields(&[
+            serialize_into_fields(&first, TracingOptions::default()).unwrap(),
+            serialize_into_fields(&second, TracingOptions::default()).unwrap(),
+        ])
+        .unwrap();
+
+        serialize_into_arrays(&fields, &first).unwrap();
+        serialize_into_arrays(&fields, &second).unwrap();
+    }
+
+    #[test]
+    fn numeric_strategies_are_kept() {
+        let schemas = vec![
+            vec![
+                Field::new("a", DataType::Int32, false).with_metadata(Strategy::StrAsNumber.into())
+            ],
+            vec![Field::new("a", DataType::Int64, false)],
+        ];
+
+        let actual = merge_fields(&schemas).unwrap();
+        let expected = vec![
+            Field::new("a", DataType::Int64, false).with_metadata(Strategy::StrAsNumber.into())
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn integers_and_dates_are_not_merged() {
+        let schemas = vec![
+            vec![Field::new("a", DataType::Int64, false)],
+            vec![Field::new("a", DataType::Date64, false)],
+        ];
+        assert!(merge_fields(&schemas).is_err());
+
+        let schemas = vec![
+            vec![Field::new("a", DataType::Date32, false)],
+            vec![Field::new("a", DataType::Int32, false)],
+        ];
+        assert!(merge_fields(&schemas).is_err());
+    }
+
+    #[test]
+    fn strings_are_widened_to_dictionaries() {
+        let dictionary = DataType::Dictionary(
+            crate::_impl::arrow2::datatypes::IntegerType::UInt32,
+            Box::new(DataType::LargeUtf8),
+            false,
+        );
+        let schemas = vec![
+            vec![Field::new("a", DataType::Utf8, false)],
+            vec![Field::new("a", dictionary.clone(), false)],
+        ];
+
+        let actual = merge_fields(&schemas).unwrap();
+        let expected = vec![Field::new("a", dictionary, false)];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn u64_and_signed_integers_give_floats() {
+        let schemas = vec![
+            vec![Field::new("a", DataType::UInt64, false)],
+            vec![Field::new("a", DataType::Int8, false)],
+        ];
+
+        let actual = merge_fields(&schemas).unwrap();
+        let expected = vec![Field::new("a", DataType::Float64, false)
+            .with_metadata(Strategy::InconsistentTypes.into())];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn union_variants_are_unified() {
+        #[derive(Serialize)]
+        struct Record {
+            value: Value,
+        }
+
+        #[derive(Serialize)]
+        enum Value {
+            Int(i32),
+            Str(String),
+            Bool(bool),
+        }
+
+        let options = TracingOptions::default().allow_null_fields(true);
+        let first = serialize_into_fields(
+            &[Record {
+                value: Value::Int(1),
+            }],
+            options.clone(),
+        )
+        .unwrap();
+        let second = serialize_into_fields(
+            &[Record {
+                value: Value::Str(String::from("foo")),
+            }],
+            options.clone(),
+        )
+        .unwrap();
+        let third = serialize_into_fields(
+            &[
+                Record {
+                    value: Value::Int(2),
+                },
+                Record {
+                    value: Value::Bool(true),
+                },
+            ],
+            options.clone(),
+        )
+        .unwrap();
+
+        let all = serialize_into_fields(
+            &[
+                Record {
+                    value: Value::Int(1),
+                },
+                Record {
+                    value: Value::Str(String::from("foo")),
+                },
+                Record {
+                    value: Value::Bool(true),
+                },
+            ],
+            options,
+        )
+        .unwrap();
+
+        let actual = merge_fields(&[first, second, third]).unwrap();
+        assert_eq!(actual, all);
+    }
+
+    #[test]
+    fn conflicts_include_the_path() {
+        let schemas = vec![
+            vec![Field::new(
+                "a",
+                DataType::Struct(vec![Field::new("b", DataType::Boolean, false)]),
+                false,
+            )],
+            vec![Field::new(
+                "a",
+                DataType::Struct(vec![Field::new("b", DataType::Utf8, false)]),
+                false,
+            )],
+        ];
+
+        let err = merge_fields(&schemas).unwrap_err();
+        assert!(err.to_string().contains("$.a.b"), "{err}");
+    }
+
+    #[test]
+    fn strings_and_lists_are_widened() {
+        let schemas = vec![
+            vec![
+                Field::new("a", DataType::Utf8, false),
+                Field::new(
+                    "b",
+                    DataType::List(Box::new(Field::new("element", DataType::UInt8, false))),
+                    false,
+                ),
+            ],
+            vec![
+                Field::new("a", DataType::LargeUtf8, false),
+                Field::new(
+                    "b",
+                    DataType::LargeList(Box::new(Field::new("element", DataType::UInt16, false))),
+                    false,
+                ),
+            ],
+        ];
+
+        let actual = merge_fields(&schemas).unwrap();
+        let expected = vec![
+            Field::new("a", DataType::LargeUtf8, false),
+            Field::new(
+                "b",
+                DataType::LargeList(Box::new(Field::new("element", DataType::UInt16, false))),
+                false,
+            ),
+        ];
+        assert_eq!(actual, expected);
+    }
+}
+
+mod overwrite {
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        _impl::arrow2::datatypes::{DataType, Field},
+        arrow2::{deserialize_from_arrays, serialize_into_arrays, serialize_into_fields},
+        schema::{Strategy, TracingOptions},
+    };
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Outer {
+        a: Option<u32>,
+        b: Nested,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Nested {
+        dt: String,
+        values: Vec<u8>,
+    }
+
+    #[test]
+    fn nested_fields() {
+        let items = vec![Outer {
+            a: None,
+            b: Nested {
+                dt: String::from("2023-01-31T12:00:00"),
+                values: vec![1, 2],
+            },
+        }];
+
+        let options = TracingOptions::default()
+            .overwrite("a", &Field::new("a", DataType::Int64, true))
+            .unwrap()
+            .overwrite(
+                "b.dt",
+                &Field::new("dt", DataType::Date64, false)
+                    .with_metadata(Strategy::NaiveStrAsDate64.into()),
+            )
+            .unwrap()
+            .overwrite(
+                "b.values",
+                &Field::new(
+                    "ignored",
+                    DataType::LargeList(Box::new(Field::new("element", DataType::UInt16, false))),
+                    false,
+                ),
+            )
+            .unwrap();
+
+        let fields = serialize_into_fields(&items, options).unwrap();
+        let expected = vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new(
+                "b",
+                DataType::Struct(vec![
+                    Field::new("dt", DataType::Date64, false)
+                        .with_metadata(Strategy::NaiveStrAsDate64.into()),
+                    Field::new(
+                        "values",
+                        DataType::LargeList(Box::new(Field::new(
+                            "element",
+                            DataType::UInt16,
+                            false,
+                        ))),
+                        false,
+                    ),
+                ]),
+                false,
+            ),
+        ];
+        assert_eq!(fields, expected);
+
+        let arrays = serialize_into_arrays(&fields, &items).unwrap();
+        let round_trip: Vec<Outer> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        assert_eq!(round_trip, items);
+    }
+
+    #[test]
+    fn unknown_paths_are_ignored() {
+        let items = vec![Outer {
+            a: Some(1),
+            b: Nested {
+                dt: String::from("foo"),
+                values: vec![3],
+            },
+        }];
+
+        let options = TracingOptions::default()
+            .overwrite("c", &Field::new("c", DataType::Int64, true))
+            .unwrap();
+
+        let actual = serialize_into_fields(&items, options).unwrap();
+        let expected = serialize_into_fields(&items, TracingOptions::default()).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn invalid_fields_are_rejected() {
+        let field =
+            Field::new("dt", DataType::Int64, false).with_metadata(Strategy::MapAsStruct.into());
+        assert!(TracingOptions::default().overwrite("dt", &field).is_err());
+    }
+}
+
+#[test]
+fn dictionary_key_type_auto_widths() {
+    use crate::{arrow2::serialize_into_field, schema::DictionaryKeyType};
+
+    let options = TracingOptions::default()
+        .string_dictionary_encoding(true)
+        .dictionary_key_type(DictionaryKeyType::Auto);
+
+    for (num_values, expected) in [
+        (256, DataType::UInt8),
+        (257, DataType::UInt16),
+        (65536, DataType::UInt16),
+        (65537, DataType::UInt32),
+        (70000, DataType::UInt32),
+    ] {
+        let items = (0..num_values).map(|i| i.to_string()).collect::<Vec<_>>();
+        let field = serialize_into_field(&items, "root", options.clone()).unwrap();
+
+        let DataType::Dictionary(key, _, _) = field.data_type else {
+            panic!("expected a dictionary, got {:?}", field.data_type);
+        };
+        assert_eq!(DataType::from(key), expected, "{num_values}");
+    }
+}
+
+#[test]
+fn distinct_values_are_only_tracked_for_dictionaries() {
+    use crate::internal::{
+        event::Event,
+        schema::{DictionaryKeyType, PrimitiveTracer},
+        sink::EventSink,
+    };
+
+    for (options, tracked) in [
+        (TracingOptions::default(), false),
+        (
+            TracingOptions::default().dictionary_key_type(DictionaryKeyType::Auto),
+            false,
+        ),
+        (
+            TracingOptions::default()
+                .string_dictionary_encoding(true)
+                .dictionary_key_type(DictionaryKeyType::Auto),
+            true,
+        ),
+        (
+            TracingOptions::default().auto_dictionary_encoding(true),
+            true,
+        ),
+    ] {
+        let mut tracer = PrimitiveTracer::new(false, &options);
+        tracer.accept(Event::Str("foo")).unwrap();
+        tracer.accept(Event::Str("bar")).unwrap();
+        assert_eq!(tracer.distinct_values.len(), if tracked { 2 } else { 0 });
+    }
+}
+
+mod serde_arrow_schema {
+    use std::collections::HashMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::{
+        _impl::arrow2::datatypes::{DataType, Field},
+        arrow2::serialize_into_fields,
+        schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy, TracingOptions},
+    };
+
+    #[derive(Serialize, Deserialize)]
+    struct Record {
+        a: Option<u8>,
+        b: Vec<String>,
+        c: (i32, f64),
+        d: HashMap<String, bool>,
+        e: Value,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    enum Value {
+        Int(i64),
+        Str(String),
+    }
+
+    fn example() -> Vec<Record> {
+        vec![
+            Record {
+                a: Some(1),
+                b: vec![String::from("x")],
+                c: (2, 3.0),
+                d: HashMap::from([(String::from("k"), true)]),
+                e: Value::Int(4),
+            },
+            Record {
+                a: None,
+                b: vec![],
+                c: (5, 6.0),
+                d: HashMap::new(),
+                e: Value::Str(String::from("y")),
+            },
+        ]
+    }
+
+    #[test]
+    fn from_samples_matches_fields() {
+        let options = TracingOptions::default().string_dictionary_encoding(true);
+        let schema = SerdeArrowSchema::from_samples(&example(), options.clone()).unwrap();
+        let fields = serialize_into_fields(&example(), options).unwrap();
+
+        assert_eq!(schema.to_arrow2_fields().unwrap(), fields);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let options = TracingOptions::default().string_dictionary_encoding(true);
+        let fields = serialize_into_fields(&example(), options).unwrap();
+
+        let schema = SerdeArrowSchema::from_arrow2_fields(&fields).unwrap();
+        assert_eq!(schema.fields.len(), 5);
+        schema.validate().unwrap();
+
+        let roundtripped = Vec::<Field>::try_from(&schema).unwrap();
+        assert_eq!(roundtripped, fields);
+        assert_eq!(
+            SerdeArrowSchema::try_from(roundtripped.as_slice()).unwrap(),
+            schema
+        );
+    }
+
+    #[test]
+    fn from_type() {
+        let schema = SerdeArrowSchema::from_type::<Record>(TracingOptions::default()).unwrap();
+        let names = schema
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(schema.fields[0].data_type, GenericDataType::U8);
+        assert!(schema.fields[0].nullable);
+    }
+
+    #[test]
+    fn manual_construction() {
+        let schema = SerdeArrowSchema::new()
+            .with_field(GenericField::new("a", GenericDataType::I64, false))
+            .with_field(
+                GenericField::new("b", GenericDataType::Date64, true)
+                    .with_strategy(Strategy::UtcStrAsDate64),
+            );
+
+        let expected = vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::Date64, true).with_metadata(Strategy::UtcStrAsDate64.into()),
+        ];
+        assert_eq!(schema.to_arrow2_fields().unwrap(), expected);
+    }
+
+    #[test]
+    fn invalid_schemas_are_rejected() {
+        let schema = SerdeArrowSchema::new().with_field(GenericField::new(
+            "a",
+            GenericDataType::List,
+            false,
+        ));
+        assert!(schema.validate().is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_roundtrip() {
+        let options = TracingOptions::default()
+            .string_dictionary_encoding(true)
+            .untagged_unions(true);
+        let schema = SerdeArrowSchema::from_samples(&example(), options).unwrap();
+
+        let json = schema.to_json().unwrap();
+        let roundtripped = SerdeArrowSchema::from_json(&json).unwrap();
+        assert_eq!(roundtripped, schema);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_format() {
+        let schema = SerdeArrowSchema::new()
+            .with_field(GenericField::new("a", GenericDataType::U8, true))
+            .with_field(
+                GenericField::new("b", GenericDataType::Date64, false)
+                    .with_strategy(Strategy::NaiveStrAsDate64),
+            );
+
+        let actual: serde_json::Value = serde_json::from_str(&schema.to_json().unwrap()).unwrap();
+        let expected = serde_json::json!({
+            "fields": [
+                {"name": "a", "data_type": "U8", "nullable": true},
+                {"name": "b", "data_type": "Date64", "strategy": "NaiveStrAsDate64"},
+            ],
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_nested() {
+        let json = r#"{
+            "fields": [
+                {
+                    "name": "items",
+                    "data_type": "LargeList",
+                    "children": [{"name": "element", "data_type": "I32"}]
+                }
+            ]
+        }"#;
+        let schema = SerdeArrowSchema::from_json(json).unwrap();
+        let expected = vec![Field::new(
+            "items",
+            DataType::LargeList(Box::new(Field::new("element", DataType::Int32, false))),
+            false,
+        )];
+        assert_eq!(schema.to_arrow2_fields().unwrap(), expected);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_unknown_strategy() {
+        let json = r#"{"fields": [{"name": "a", "data_type": "Date64", "strategy": "Foo"}]}"#;
+        let err = SerdeArrowSchema::from_json(json).unwrap_err();
+        assert!(err.to_string().contains("Unknown strategy Foo"), "{err}");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_invalid_schema() {
+        let json = r#"{"fields": [{"name": "a", "data_type": "LargeList"}]}"#;
+        assert!(SerdeArrowSchema::from_json(json).is_err());
+    }
+}
+
+mod diff {
+    use serde::Serialize;
+
+    use crate::{
+        _impl::arrow2::datatypes::{DataType, Field},
+        schema::{
+            diff_schemas, GenericDataType, GenericField, SchemaDifference, SerdeArrowSchema,
+            Strategy, TracingOptions,
+        },
+    };
+
+    #[derive(Serialize)]
+    struct Record {
+        a: u32,
+        b: Nested,
+        c: Vec<Nested>,
+    }
+
+    #[derive(Serialize)]
+    struct Nested {
+        dt: String,
+        value: Option<i8>,
+    }
+
+    fn example() -> SerdeArrowSchema {
+        let items = [Record {
+            a: 1,
+            b: Nested {
+                dt: String::from("2023-01-31T12:00:00Z"),
+                value: Some(2),
+            },
+            c: vec![Nested {
+                dt: String::from("2023-01-31T12:00:00Z"),
+                value: Some(3),
+            }],
+        }];
+        SerdeArrowSchema::from_samples(&items, TracingOptions::default()).unwrap()
+    }
+
+    #[test]
+    fn identical_schemas() {
+        let diff = diff_schemas(&example(), &example());
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "");
+    }
+
+    #[test]
+    fn nested_differences() {
+        let expected = example();
+        let mut actual = example();
+
+        actual.fields[0].data_type = GenericDataType::U64;
+        actual.fields[1].children[0] = GenericField::new("dt", GenericDataType::Date64, false)
+            .with_strategy(Strategy::UtcStrAsDate64);
+        actual.fields[2].children[0].children[1].nullable = false;
+        actual.fields[2].children[0].children.remove(0);
+        actual
+            .fields
+            .push(GenericField::new("d", GenericDataType::Bool, false));
+
+        let diff = diff_schemas(&expected, &actual);
+        let expected_diff = [
+            "$.a: data type differs (expected U32, actual U64)",
+            "$.b.dt: data type differs (expected LargeUtf8, actual Date64)",
+            "$.b.dt: strategy differs (expected none, actual UtcStrAsDate64)",
+            "$.c.element.dt: missing field",
+            "$.c.element.value: nullability differs (expected nullable, actual non-nullable)",
+            "$.d: extra field",
+        ];
+        assert_eq!(diff.to_string(), expected_diff.join("\n"));
+        assert_eq!(
+            diff.differences[0],
+            SchemaDifference::DataType {
+                path: String::from("$.a"),
+                expected: GenericDataType::U32,
+                actual: GenericDataType::U64,
+            }
+        );
+    }
+
+    #[test]
+    fn positional_children() {
+        let expected = SerdeArrowSchema::from_arrow2_fields(&[Field::new(
+            "list",
+            DataType::LargeList(Box::new(Field::new("element", DataType::Int32, false))),
+            false,
+        )])
+        .unwrap();
+        let actual = SerdeArrowSchema::from_arrow2_fields(&[Field::new(
+            "list",
+            DataType::LargeList(Box::new(Field::new("item", DataType::Int32, false))),
+            false,
+        )])
+        .unwrap();
+
+        let diff = diff_schemas(&expected, &actual);
+        assert_eq!(
+            diff.to_string(),
+            r#"$.list.element: name differs (expected "element", actual "item")"#
+        );
+    }
+
+    #[test]
+    fn compatibility_errors_include_diff() {
+        let expected = GenericField::new("root", GenericDataType::Struct, false)
+            .with_child(GenericField::new("a", GenericDataType::Bool, false));
+        let actual = GenericField::new("root", GenericDataType::Struct, false)
+            .with_child(GenericField::new("a", GenericDataType::LargeUtf8, false));
+
+        let err = expected.validate_compatibility(&actual).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("$.a: data type differs (expected Bool, actual LargeUtf8)"),
+            "{err}"
+        );
+    }
+}
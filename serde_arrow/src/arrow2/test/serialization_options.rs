@@ -0,0 +1,927 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    _impl::arrow2::{
+        array::Array,
+        datatypes::{DataType, Field},
+    },
+    arrow2::{
+        deserialize_from_arrays, deserialize_from_arrays_with_options, serialize_into_arrays,
+        serialize_into_fields, ArraysBuilder,
+    },
+    internal::{serialization::CompilationOptions, source::DeserializationOptions},
+    schema::{Strategy, TracingOptions},
+};
+
+mod schema_evolution {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize)]
+    struct Old {
+        a: i64,
+        nested: OldNested,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    struct OldNested {
+        b: bool,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct New {
+        a: i64,
+        c: Option<String>,
+        nested: NewNested,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct NewNested {
+        b: bool,
+        d: Option<Vec<u8>>,
+        e: Option<NewInner>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct NewInner {
+        f: u32,
+    }
+
+    fn new_fields() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("c", DataType::LargeUtf8, true),
+            Field::new(
+                "nested",
+                DataType::Struct(vec![
+                    Field::new("b", DataType::Boolean, false),
+                    Field::new(
+                        "d",
+                        DataType::LargeList(Box::new(Field::new(
+                            "element",
+                            DataType::UInt8,
+                            false,
+                        ))),
+                        true,
+                    ),
+                    Field::new(
+                        "e",
+                        DataType::Struct(vec![Field::new("f", DataType::UInt32, false)]),
+                        true,
+                    ),
+                ]),
+                false,
+            ),
+        ]
+    }
+
+    #[test]
+    fn missing_nullable_fields_are_filled_with_nulls() {
+        let items = [
+            Old {
+                a: 1,
+                nested: OldNested { b: true },
+            },
+            Old {
+                a: 2,
+                nested: OldNested { b: false },
+            },
+        ];
+
+        let fields = new_fields();
+        let arrays = serialize_into_arrays(&fields, &items).unwrap();
+        assert_eq!(arrays[1].null_count(), 2);
+
+        let actual: Vec<New> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![
+            New {
+                a: 1,
+                c: None,
+                nested: NewNested {
+                    b: true,
+                    d: None,
+                    e: None,
+                },
+            },
+            New {
+                a: 2,
+                c: None,
+                nested: NewNested {
+                    b: false,
+                    d: None,
+                    e: None,
+                },
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn missing_nullable_lists_and_maps_are_filled_with_nulls() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Expected {
+            b: bool,
+            list: Option<Vec<u8>>,
+            map: Option<BTreeMap<String, u8>>,
+        }
+
+        let items = [OldNested { b: true }];
+        let fields = vec![
+            Field::new("b", DataType::Boolean, false),
+            Field::new(
+                "list",
+                DataType::List(Box::new(Field::new("element", DataType::UInt8, false))),
+                true,
+            ),
+            Field::new(
+                "map",
+                DataType::Map(
+                    Box::new(Field::new(
+                        "entries",
+                        DataType::Struct(vec![
+                            Field::new("key", DataType::LargeUtf8, false),
+                            Field::new("value", DataType::UInt8, false),
+                        ]),
+                        false,
+                    )),
+                    false,
+                ),
+                true,
+            ),
+        ];
+
+        let arrays = serialize_into_arrays(&fields, &items).unwrap();
+        let actual: Vec<Expected> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![Expected {
+            b: true,
+            list: None,
+            map: None,
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn missing_non_nullable_fields_are_an_error() {
+        let items = [OldNested { b: true }];
+        let fields = vec![
+            Field::new("b", DataType::Boolean, false),
+            Field::new("g", DataType::Int32, false),
+        ];
+
+        let err = serialize_into_arrays(&fields, &items).unwrap_err();
+        assert!(err.to_string().contains("missing non-nullable field g"));
+    }
+
+    #[test]
+    fn unknown_fields_are_an_error_per_default() {
+        let items = [Old {
+            a: 1,
+            nested: OldNested { b: true },
+        }];
+        let fields = vec![Field::new("a", DataType::Int64, false)];
+
+        let err = serialize_into_arrays(&fields, &items).unwrap_err();
+        assert!(err.to_string().contains("nested"));
+    }
+
+    #[test]
+    fn unknown_fields_are_ignored() {
+        #[derive(Debug, Serialize)]
+        struct Record {
+            removed: Vec<OldNested>,
+            a: i64,
+            nested: Nested,
+            also_removed: Option<(u8, String)>,
+        }
+
+        #[derive(Debug, Serialize)]
+        struct Nested {
+            removed: BTreeMap<String, OldNested>,
+            b: bool,
+            also_removed: E,
+        }
+
+        #[derive(Debug, Serialize)]
+        enum E {
+            A(OldNested),
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Expected {
+            a: i64,
+            nested: OldNestedDe,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct OldNestedDe {
+            b: bool,
+        }
+
+        let items = [
+            Record {
+                removed: vec![OldNested { b: true }],
+                a: 1,
+                nested: Nested {
+                    removed: BTreeMap::from([(String::from("x"), OldNested { b: false })]),
+                    b: true,
+                    also_removed: E::A(OldNested { b: true }),
+                },
+                also_removed: Some((0, String::from("foo"))),
+            },
+            Record {
+                removed: vec![],
+                a: 2,
+                nested: Nested {
+                    removed: BTreeMap::new(),
+                    b: false,
+                    also_removed: E::A(OldNested { b: false }),
+                },
+                also_removed: None,
+            },
+        ];
+
+        let fields = vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new(
+                "nested",
+                DataType::Struct(vec![Field::new("b", DataType::Boolean, false)]),
+                false,
+            ),
+        ];
+
+        let options = CompilationOptions::default().ignore_unknown_fields(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        builder.extend(&items).unwrap();
+        let arrays = builder.build_arrays().unwrap();
+
+        let actual: Vec<Expected> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![
+            Expected {
+                a: 1,
+                nested: OldNestedDe { b: true },
+            },
+            Expected {
+                a: 2,
+                nested: OldNestedDe { b: false },
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unknown_fields_of_the_last_union_variant_are_ignored() {
+        #[derive(Debug, Serialize)]
+        enum Old {
+            A(u8),
+            B { x: u8, removed: bool },
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        enum New {
+            A(u8),
+            B { x: u8 },
+        }
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Record<T> {
+            value: T,
+            after: u8,
+        }
+
+        let items = [
+            Record {
+                value: Old::B {
+                    x: 1,
+                    removed: true,
+                },
+                after: 4,
+            },
+            Record {
+                value: Old::A(2),
+                after: 5,
+            },
+            Record {
+                value: Old::B {
+                    x: 3,
+                    removed: false,
+                },
+                after: 6,
+            },
+        ];
+        let expected = [
+            Record {
+                value: New::B { x: 1 },
+                after: 4,
+            },
+            Record {
+                value: New::A(2),
+                after: 5,
+            },
+            Record {
+                value: New::B { x: 3 },
+                after: 6,
+            },
+        ];
+
+        let fields = serialize_into_fields(&expected, TracingOptions::default()).unwrap();
+
+        let options = CompilationOptions::default().ignore_unknown_fields(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        builder.extend(&items).unwrap();
+        let arrays = builder.build_arrays().unwrap();
+
+        let actual: Vec<Record<New>> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unknown_keys_of_maps_as_structs_are_ignored() {
+        let items = [
+            BTreeMap::from([(String::from("a"), 1_u32), (String::from("b"), 2)]),
+            BTreeMap::from([(String::from("b"), 3_u32), (String::from("c"), 4)]),
+        ];
+        let fields = vec![Field::new(
+            "map",
+            DataType::Struct(vec![
+                Field::new("a", DataType::UInt32, true),
+                Field::new("c", DataType::UInt32, true),
+            ]),
+            false,
+        )
+        .with_metadata(Strategy::MapAsStruct.into())];
+
+        #[derive(Debug, Serialize)]
+        struct Record<'a> {
+            map: &'a BTreeMap<String, u32>,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Expected {
+            map: BTreeMap<String, Option<u32>>,
+        }
+
+        let items = items.iter().map(|map| Record { map }).collect::<Vec<_>>();
+
+        let options = CompilationOptions::default().ignore_unknown_fields(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        builder.extend(&items).unwrap();
+        let arrays = builder.build_arrays().unwrap();
+
+        let actual: Vec<Expected> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![
+            Expected {
+                map: BTreeMap::from([(String::from("a"), Some(1)), (String::from("c"), None)]),
+            },
+            Expected {
+                map: BTreeMap::from([(String::from("a"), None), (String::from("c"), Some(4))]),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+}
+
+mod fill_missing_with_defaults {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize)]
+    struct Old {
+        a: i32,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct New {
+        a: i32,
+        b: f64,
+        c: bool,
+        d: String,
+        e: String,
+        f: Vec<u8>,
+        g: Inner,
+        h: BTreeMap<String, u8>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Inner {
+        x: u8,
+        y: Option<u8>,
+    }
+
+    fn new_fields() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Float64, false),
+            Field::new("c", DataType::Boolean, false),
+            Field::new("d", DataType::LargeUtf8, false),
+            Field::new("e", DataType::Utf8, false),
+            Field::new(
+                "f",
+                DataType::LargeList(Box::new(Field::new("element", DataType::UInt8, false))),
+                false,
+            ),
+            Field::new(
+                "g",
+                DataType::Struct(vec![
+                    Field::new("x", DataType::UInt8, false),
+                    Field::new("y", DataType::UInt8, true),
+                ]),
+                false,
+            ),
+            Field::new(
+                "h",
+                DataType::Map(
+                    Box::new(Field::new(
+                        "entries",
+                        DataType::Struct(vec![
+                            Field::new("key", DataType::LargeUtf8, false),
+                            Field::new("value", DataType::UInt8, false),
+                        ]),
+                        false,
+                    )),
+                    false,
+                ),
+                false,
+            ),
+        ]
+    }
+
+    #[test]
+    fn missing_fields_are_filled() {
+        let fields = new_fields();
+        let options = CompilationOptions::default().fill_missing_with_defaults(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        builder.extend(&[Old { a: 1 }, Old { a: 2 }]).unwrap();
+
+        let defaulted = builder.defaulted_fields();
+        let expected = ["$.b", "$.c", "$.d", "$.e", "$.f", "$.g", "$.h"]
+            .into_iter()
+            .map(|path| (String::from(path), 2))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(defaulted, expected);
+
+        let arrays = builder.build_arrays().unwrap();
+        assert!(builder.defaulted_fields().is_empty());
+
+        let actual: Vec<New> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = (1..=2)
+            .map(|a| New {
+                a,
+                b: 0.0,
+                c: false,
+                d: String::new(),
+                e: String::new(),
+                f: vec![],
+                g: Inner { x: 0, y: None },
+                h: BTreeMap::new(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn nested_fields_are_filled() {
+        #[derive(Debug, PartialEq, Serialize)]
+        struct Record {
+            g: OldInner,
+        }
+
+        #[derive(Debug, PartialEq, Serialize)]
+        struct OldInner {
+            y: Option<u8>,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Expected {
+            g: Inner,
+        }
+
+        let fields = vec![new_fields().swap_remove(6)];
+        let options = CompilationOptions::default().fill_missing_with_defaults(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        builder
+            .extend(&[
+                Record {
+                    g: OldInner { y: Some(3) },
+                },
+                Record {
+                    g: OldInner { y: None },
+                },
+            ])
+            .unwrap();
+
+        assert_eq!(
+            builder.defaulted_fields(),
+            BTreeMap::from([(String::from("$.g.x"), 2)])
+        );
+
+        let arrays = builder.build_arrays().unwrap();
+        let actual: Vec<Expected> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![
+            Expected {
+                g: Inner { x: 0, y: Some(3) },
+            },
+            Expected {
+                g: Inner { x: 0, y: None },
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn missing_keys_of_maps_as_structs_are_filled() {
+        #[derive(Debug, Serialize)]
+        struct Record {
+            map: BTreeMap<String, u32>,
+        }
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Expected {
+            map: BTreeMap<String, u32>,
+        }
+
+        let fields = vec![Field::new(
+            "map",
+            DataType::Struct(vec![
+                Field::new("a", DataType::UInt32, false),
+                Field::new("b", DataType::UInt32, false),
+            ]),
+            false,
+        )
+        .with_metadata(Strategy::MapAsStruct.into())];
+
+        let options = CompilationOptions::default().fill_missing_with_defaults(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        builder
+            .extend(&[
+                Record {
+                    map: BTreeMap::from([(String::from("a"), 1)]),
+                },
+                Record {
+                    map: BTreeMap::from([(String::from("a"), 2), (String::from("b"), 3)]),
+                },
+            ])
+            .unwrap();
+
+        assert_eq!(
+            builder.defaulted_fields(),
+            BTreeMap::from([(String::from("$.map.b"), 1)])
+        );
+
+        let arrays = builder.build_arrays().unwrap();
+        let actual: Vec<Expected> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![
+            Expected {
+                map: BTreeMap::from([(String::from("a"), 1), (String::from("b"), 0)]),
+            },
+            Expected {
+                map: BTreeMap::from([(String::from("a"), 2), (String::from("b"), 3)]),
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn unions_are_not_filled() {
+        let fields = vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new(
+                "b",
+                DataType::Union(
+                    vec![
+                        Field::new("A", DataType::Int32, false),
+                        Field::new("B", DataType::Boolean, false),
+                    ],
+                    None,
+                    crate::_impl::arrow2::datatypes::UnionMode::Dense,
+                ),
+                false,
+            ),
+        ];
+        let options = CompilationOptions::default().fill_missing_with_defaults(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+
+        let err = builder.push(&Old { a: 1 }).unwrap_err();
+        assert!(err.to_string().contains("missing non-nullable field b"));
+    }
+}
+
+mod coerce_numbers {
+    use super::*;
+
+    #[derive(Debug, Serialize)]
+    struct Record {
+        a: i64,
+        b: u16,
+        c: bool,
+        d: i32,
+        e: Option<f64>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Expected {
+        a: i32,
+        b: u8,
+        c: i8,
+        d: f32,
+        e: Option<f32>,
+    }
+
+    fn fields() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::UInt8, false),
+            Field::new("c", DataType::Int8, false),
+            Field::new("d", DataType::Float32, false),
+            Field::new("e", DataType::Float32, true),
+        ]
+    }
+
+    fn build(fields: &[Field], items: &[Record]) -> crate::Result<Vec<Box<dyn Array>>> {
+        let options = CompilationOptions::default().coerce_numbers(true);
+        let mut builder = ArraysBuilder::new_with_options(fields, options)?;
+        builder.extend(items)?;
+        builder.build_arrays()
+    }
+
+    #[test]
+    fn values_are_converted() {
+        let items = [
+            Record {
+                a: -3,
+                b: 255,
+                c: true,
+                d: -7,
+                e: Some(1.5),
+            },
+            Record {
+                a: i32::MAX as i64,
+                b: 0,
+                c: false,
+                d: 1 << 20,
+                e: None,
+            },
+        ];
+
+        let fields = fields();
+        let arrays = build(&fields, &items).unwrap();
+        let actual: Vec<Expected> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![
+            Expected {
+                a: -3,
+                b: 255,
+                c: 1,
+                d: -7.0,
+                e: Some(1.5),
+            },
+            Expected {
+                a: i32::MAX,
+                b: 0,
+                c: 0,
+                d: 1048576.0,
+                e: None,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn out_of_range_values_are_an_error() {
+        let items = [Record {
+            a: 1,
+            b: 256,
+            c: false,
+            d: 0,
+            e: None,
+        }];
+
+        let err = build(&fields(), &items).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot coerce 256 into U8 for field $.b: value out of range"));
+
+        let items = [Record {
+            a: 1,
+            b: 0,
+            c: false,
+            d: 0,
+            e: Some(1e300),
+        }];
+
+        let err = build(&fields(), &items).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("into F32 for field $.e: value out of range"));
+    }
+
+    #[test]
+    fn floats_are_not_converted_to_integers() {
+        let fields = vec![Field::new(
+            "nested",
+            DataType::Struct(vec![Field::new("value", DataType::Int64, false)]),
+            false,
+        )];
+
+        #[derive(Serialize)]
+        struct Record {
+            nested: Nested,
+        }
+
+        #[derive(Serialize)]
+        struct Nested {
+            value: f64,
+        }
+
+        let options = CompilationOptions::default().coerce_numbers(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        let err = builder
+            .push(&Record {
+                nested: Nested { value: 1.5 },
+            })
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot coerce the float 1.5 into I64 for field $.nested.value"));
+    }
+
+    #[test]
+    fn numbers_are_not_coerced_per_default() {
+        let items = [Record {
+            a: 1,
+            b: 2,
+            c: false,
+            d: 0,
+            e: None,
+        }];
+        let fields = fields();
+
+        assert!(serialize_into_arrays(&fields, &items).is_err());
+    }
+}
+
+mod str_as_number {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Record {
+        a: String,
+        b: Option<String>,
+        c: String,
+        d: String,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Parsed {
+        a: i32,
+        b: Option<u8>,
+        c: f64,
+        d: bool,
+    }
+
+    fn fields() -> Vec<Field> {
+        vec![
+            Field::new("a", DataType::Int32, false).with_metadata(Strategy::StrAsNumber.into()),
+            Field::new("b", DataType::UInt8, true).with_metadata(Strategy::StrAsNumber.into()),
+            Field::new("c", DataType::Float64, false).with_metadata(Strategy::StrAsNumber.into()),
+            Field::new("d", DataType::Boolean, false).with_metadata(Strategy::StrAsBool.into()),
+        ]
+    }
+
+    fn record(a: &str, b: Option<&str>, c: &str, d: &str) -> Record {
+        Record {
+            a: a.to_string(),
+            b: b.map(str::to_string),
+            c: c.to_string(),
+            d: d.to_string(),
+        }
+    }
+
+    #[test]
+    fn strings_are_parsed() {
+        let items = [
+            record("42", Some("255"), "1.5e3", "true"),
+            record("-1e3", None, "-2", "false"),
+        ];
+
+        let fields = fields();
+        let arrays = serialize_into_arrays(&fields, &items).unwrap();
+        let actual: Vec<Parsed> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![
+            Parsed {
+                a: 42,
+                b: Some(255),
+                c: 1500.0,
+                d: true,
+            },
+            Parsed {
+                a: -1000,
+                b: None,
+                c: -2.0,
+                d: false,
+            },
+        ];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn numbers_are_accepted() {
+        #[derive(Debug, Serialize)]
+        struct Numbers {
+            a: u64,
+            b: Option<i8>,
+            c: f32,
+            d: bool,
+        }
+
+        let items = [Numbers {
+            a: 7,
+            b: Some(3),
+            c: 0.5,
+            d: true,
+        }];
+
+        let fields = fields();
+        let arrays = serialize_into_arrays(&fields, &items).unwrap();
+        let actual: Vec<Parsed> = deserialize_from_arrays(&fields, &arrays).unwrap();
+        let expected = vec![Parsed {
+            a: 7,
+            b: Some(3),
+            c: 0.5,
+            d: true,
+        }];
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn invalid_strings_are_an_error() {
+        let fields = fields();
+
+        let err = serialize_into_arrays(&fields, &[record("foo", None, "0", "true")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot parse \"foo\" as I32 for field $.a"));
+
+        let err = serialize_into_arrays(&fields, &[record("1.5", None, "0", "true")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot parse \"1.5\" as I32 for field $.a"));
+
+        let err =
+            serialize_into_arrays(&fields, &[record("0", Some("256"), "0", "true")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot coerce 256 into U8 for field $.b: value out of range"));
+
+        let err = serialize_into_arrays(&fields, &[record("0", None, "0", "yes")]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot parse \"yes\" as Bool for field $.d"));
+    }
+
+    #[test]
+    fn numbers_can_be_formatted_as_strings() {
+        let items = [
+            record("42", Some("7"), "1.5", "true"),
+            record("-3", None, "2", "false"),
+        ];
+
+        let fields = fields();
+        let arrays = serialize_into_arrays(&fields, &items).unwrap();
+
+        let options = DeserializationOptions::default().format_numbers_as_strings(true);
+        let actual: Vec<Record> =
+            deserialize_from_arrays_with_options(&fields, &arrays, options).unwrap();
+        assert_eq!(actual, items);
+
+        let res = deserialize_from_arrays::<Vec<Record>, _>(&fields, &arrays);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn strategies_require_matching_data_types() {
+        let fields =
+            vec![Field::new("a", DataType::LargeUtf8, false)
+                .with_metadata(Strategy::StrAsNumber.into())];
+        let res = serialize_into_arrays(&fields, &[record("0", None, "0", "true")]);
+        assert!(res.is_err());
+
+        let fields =
+            vec![Field::new("a", DataType::Int32, false).with_metadata(Strategy::StrAsBool.into())];
+        let res = serialize_into_arrays(&fields, &[record("0", None, "0", "true")]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn strings_are_rejected_without_the_strategy() {
+        let fields = vec![Field::new("a", DataType::Int32, false)];
+        let options = CompilationOptions::default()
+            .coerce_numbers(true)
+            .ignore_unknown_fields(true);
+        let mut builder = ArraysBuilder::new_with_options(&fields, options).unwrap();
+        let err = builder
+            .extend(&[record("1", None, "0", "true")])
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot accept the string \"1\" for the I32 field $.a"));
+    }
+}
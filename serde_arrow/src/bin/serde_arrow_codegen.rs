@@ -0,0 +1,35 @@
+//! Generate Rust type definitions from a schema stored as JSON
+//!
+//! Usage: `serde_arrow_codegen <schema.json> [<type name>]`. The schema is read
+//! from stdin if the path is `-`. The type name defaults to `Record`.
+//!
+use std::io::Read;
+
+use serde_arrow::schema::SerdeArrowSchema;
+
+fn main() {
+    if let Err(err) = run(std::env::args().skip(1).collect()) {
+        eprintln!("Error: {err}");
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+    let (path, name) = match args.as_slice() {
+        [path] => (path.as_str(), "Record"),
+        [path, name] => (path.as_str(), name.as_str()),
+        _ => return Err("usage: serde_arrow_codegen <schema.json> [<type name>]".into()),
+    };
+
+    let json = if path == "-" {
+        let mut json = String::new();
+        std::io::stdin().read_to_string(&mut json)?;
+        json
+    } else {
+        std::fs::read_to_string(path)?
+    };
+
+    let schema = SerdeArrowSchema::from_json(&json)?;
+    print!("{}", schema.to_rust_code(name)?);
+    Ok(())
+}
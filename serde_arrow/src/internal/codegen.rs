@@ -0,0 +1,309 @@
+//! Generate Rust type definitions matching a schema
+//!
+use std::fmt::Write;
+
+use crate::internal::{
+    error::{fail, Result},
+    schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy},
+};
+
+const DERIVES: &str = "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]";
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "macro", "override", "priv", "try", "typeof",
+    "unsized", "virtual", "yield",
+];
+
+/// Keywords that cannot be used as raw identifiers
+const PATH_KEYWORDS: &[&str] = &["crate", "self", "super", "Self"];
+
+/// Type names used by the generated code that must not be shadowed
+const RESERVED_TYPE_NAMES: &[&str] = &[
+    "Deserialize",
+    "Option",
+    "Self",
+    "Serialize",
+    "String",
+    "Vec",
+];
+
+impl SerdeArrowSchema {
+    /// Generate Rust type definitions for records of this schema
+    ///
+    /// The records are described by a struct with the given name. Nested
+    /// structs and unions result in additional type definitions named after
+    /// their fields. The types derive `Serialize` and `Deserialize` with the
+    /// attributes required to round-trip with `serialize_into_arrays` and
+    /// `deserialize_from_arrays`. Date fields with strategies use `chrono`
+    /// types, which require its `serde` feature. Names that map to the same
+    /// identifier, e.g., `a-b` and `a_b`, are made unique with a numeric
+    /// suffix, as are type names that would shadow types used by the generated
+    /// code, e.g., `String`.
+    ///
+    pub fn to_rust_code(&self, name: &str) -> Result<String> {
+        self.validate()?;
+
+        let mut generator = CodeGenerator::default();
+        let root = GenericField {
+            name: name.to_string(),
+            data_type: GenericDataType::Struct,
+            nullable: false,
+            strategy: None,
+            children: self.fields.clone(),
+        };
+        generator.define_struct(&root, to_type_name(name))?;
+
+        let mut code = String::from("use serde::{Deserialize, Serialize};\n");
+        for definition in generator.definitions.iter().rev() {
+            writeln!(code)?;
+            code.push_str(definition);
+        }
+        Ok(code)
+    }
+}
+
+#[derive(Default)]
+struct CodeGenerator {
+    definitions: Vec<String>,
+    type_names: Vec<String>,
+}
+
+impl CodeGenerator {
+    fn reserve_type_name(&mut self, name: String) -> String {
+        let mut candidate = name.clone();
+        let mut counter = 2;
+        while self.type_names.contains(&candidate)
+            || RESERVED_TYPE_NAMES.contains(&candidate.as_str())
+        {
+            candidate = format!("{name}{counter}");
+            counter += 1;
+        }
+        self.type_names.push(candidate.clone());
+        candidate
+    }
+
+    fn define_struct(&mut self, field: &GenericField, type_name: String) -> Result<String> {
+        let type_name = self.reserve_type_name(type_name);
+
+        let mut members = Vec::new();
+        let mut idents = Vec::new();
+        for child in &field.children {
+            let ident = reserve_ident(&mut idents, to_field_ident(&child.name), "_");
+            let ty = self.field_type(child)?;
+            members.push((ident, child.name.as_str(), ty));
+        }
+
+        let mut code = String::new();
+        writeln!(code, "{DERIVES}")?;
+        writeln!(code, "pub struct {type_name} {{")?;
+        for (ident, name, ty) in members {
+            if ident.trim_start_matches("r#") != name {
+                writeln!(code, "    #[serde(rename = {name:?})]")?;
+            }
+            writeln!(code, "    pub {ident}: {ty},")?;
+        }
+        writeln!(code, "}}")?;
+
+        self.definitions.push(code);
+        Ok(type_name)
+    }
+
+    fn define_enum(&mut self, field: &GenericField, type_name: String) -> Result<String> {
+        let type_name = self.reserve_type_name(type_name);
+        let untagged = matches!(field.strategy, Some(Strategy::UntaggedUnion));
+
+        let mut variants = Vec::new();
+        // NOTE: `Self` cannot be used as a variant name
+        let mut idents = vec![String::from("Self")];
+        for (idx, child) in field.children.iter().enumerate() {
+            let (ident, rename) = if untagged {
+                (format!("Variant{idx}"), None)
+            } else {
+                let ident = reserve_ident(&mut idents, to_type_name(&child.name), "");
+                let rename = (ident != child.name).then_some(child.name.as_str());
+                (ident, rename)
+            };
+
+            let ty = if child.data_type == GenericDataType::Null {
+                None
+            } else {
+                Some(self.type_for(child, format!("{type_name}{}", to_type_name(&child.name)))?)
+            };
+            variants.push((ident, rename, ty));
+        }
+
+        let mut code = String::new();
+        writeln!(code, "{DERIVES}")?;
+        if untagged {
+            writeln!(code, "#[serde(untagged)]")?;
+        }
+        writeln!(code, "pub enum {type_name} {{")?;
+        for (ident, rename, ty) in variants {
+            if let Some(rename) = rename {
+                writeln!(code, "    #[serde(rename = {rename:?})]")?;
+            }
+            match ty {
+                Some(ty) => writeln!(code, "    {ident}({ty}),")?,
+                None => writeln!(code, "    {ident},")?,
+            }
+        }
+        writeln!(code, "}}")?;
+
+        self.definitions.push(code);
+        Ok(type_name)
+    }
+
+    fn field_type(&mut self, field: &GenericField) -> Result<String> {
+        let ty = self.type_for(field, to_type_name(&field.name))?;
+        if field.nullable && field.data_type != GenericDataType::Null {
+            Ok(format!("Option<{ty}>"))
+        } else {
+            Ok(ty)
+        }
+    }
+
+    fn type_for(&mut self, field: &GenericField, type_name: String) -> Result<String> {
+        use GenericDataType as D;
+
+        let ty = match field.data_type {
+            D::Null => String::from("()"),
+            D::Bool => String::from("bool"),
+            D::I8 => String::from("i8"),
+            D::I16 => String::from("i16"),
+            D::I32 => String::from("i32"),
+            D::I64 => String::from("i64"),
+            D::U8 => String::from("u8"),
+            D::U16 => String::from("u16"),
+            D::U32 => String::from("u32"),
+            D::U64 => String::from("u64"),
+            D::F16 => String::from("half::f16"),
+            D::F32 => String::from("f32"),
+            D::F64 => String::from("f64"),
+            D::Utf8 | D::LargeUtf8 => String::from("String"),
+            D::Date32 => match field.strategy {
+                Some(Strategy::NaiveStrAsDate32) => String::from("chrono::NaiveDate"),
+                _ => String::from("i32"),
+            },
+            D::Date64 => match field.strategy {
+                Some(Strategy::UtcStrAsDate64) => String::from("chrono::DateTime<chrono::Utc>"),
+                Some(Strategy::NaiveStrAsDate64) => String::from("chrono::NaiveDateTime"),
+                _ => String::from("i64"),
+            },
+            D::Struct if matches!(field.strategy, Some(Strategy::TupleAsStruct)) => {
+                let mut elements = Vec::new();
+                for child in &field.children {
+                    elements.push(self.field_type(child)?);
+                }
+                // NOTE: single element tuples require a trailing comma
+                let trailing = if elements.len() == 1 { "," } else { "" };
+                format!("({}{trailing})", elements.join(", "))
+            }
+            D::Struct => self.define_struct(field, type_name)?,
+            D::List | D::LargeList => {
+                let Some(item) = field.children.first() else {
+                    fail!("List field {} without item field", field.name);
+                };
+                format!("Vec<{}>", self.field_type(item)?)
+            }
+            D::Map => {
+                let Some(entries) = field.children.first() else {
+                    fail!("Map field {} without entries field", field.name);
+                };
+                let [key, value] = entries.children.as_slice() else {
+                    fail!("Map field {} must have a key and a value field", field.name);
+                };
+                format!(
+                    "std::collections::HashMap<{}, {}>",
+                    self.field_type(key)?,
+                    self.field_type(value)?
+                )
+            }
+            D::Dictionary => {
+                let Some(value) = field.children.get(1) else {
+                    fail!("Dictionary field {} without value field", field.name);
+                };
+                self.type_for(value, type_name)?
+            }
+            D::Union => self.define_enum(field, type_name)?,
+        };
+        Ok(ty)
+    }
+}
+
+/// Make an identifier unique among the given identifiers by appending a counter
+///
+/// Different names may map to the same identifier, e.g., `a-b` and `a_b`. The
+/// original names are kept via `#[serde(rename = "...")]`.
+///
+fn reserve_ident(idents: &mut Vec<String>, ident: String, separator: &str) -> String {
+    let mut candidate = ident.clone();
+    let mut counter = 2;
+    while idents.contains(&candidate) {
+        // NOTE: keywords are no longer keywords with the counter attached
+        let base = ident.trim_start_matches("r#");
+        candidate = format!("{base}{separator}{counter}");
+        counter += 1;
+    }
+    idents.push(candidate.clone());
+    candidate
+}
+
+/// Convert a field name into a valid Rust identifier in snake case
+///
+fn to_field_ident(name: &str) -> String {
+    let mut ident = String::new();
+    let mut prev_lower = false;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if c.is_ascii_uppercase() && prev_lower {
+                ident.push('_');
+            }
+            ident.push(c.to_ascii_lowercase());
+            prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        } else if !ident.ends_with('_') {
+            ident.push('_');
+            prev_lower = false;
+        }
+    }
+
+    if ident.is_empty() || ident == "_" {
+        ident = String::from("field_");
+    } else if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert_str(0, "field_");
+    }
+    if KEYWORDS.contains(&ident.as_str()) {
+        ident.insert_str(0, "r#");
+    } else if PATH_KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+/// Convert a field name into a valid Rust type name in pascal case
+///
+fn to_type_name(name: &str) -> String {
+    let mut type_name = String::new();
+    let mut upper = true;
+
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            if upper {
+                type_name.push(c.to_ascii_uppercase());
+            } else {
+                type_name.push(c);
+            }
+            upper = c.is_ascii_digit();
+        } else {
+            upper = true;
+        }
+    }
+
+    if type_name.is_empty() || type_name.starts_with(|c: char| c.is_ascii_digit()) {
+        type_name.insert(0, 'T');
+    }
+    type_name
+}
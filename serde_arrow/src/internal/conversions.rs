@@ -2,6 +2,13 @@ use half::f16;
 
 use crate::Error;
 
+/// The number of days between the common era and the unix epoch
+///
+/// Date32 stores the days since the unix epoch, chrono counts the days from the
+/// common era.
+///
+pub const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
 pub trait ToBytes: Sized {
     type Bytes;
 
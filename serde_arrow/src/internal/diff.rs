@@ -0,0 +1,263 @@
+//! Human-readable differences between schemas
+//!
+use crate::internal::schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy};
+
+/// A single difference between two schemas
+///
+/// Paths are given as dotted paths starting at the root `$`, e.g., `$.a.b`.
+/// Children of lists, maps, unions and dictionaries are addressed by their
+/// names, e.g., `$.list.element`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaDifference {
+    /// A field of the expected schema is missing in the actual schema
+    MissingField { path: String },
+    /// A field of the actual schema is not part of the expected schema
+    ExtraField { path: String },
+    /// The names of positional children (e.g., list items) differ
+    Name {
+        path: String,
+        expected: String,
+        actual: String,
+    },
+    /// The data types of the fields differ
+    DataType {
+        path: String,
+        expected: GenericDataType,
+        actual: GenericDataType,
+    },
+    /// The nullability of the fields differs
+    Nullability {
+        path: String,
+        expected: bool,
+        actual: bool,
+    },
+    /// The strategies of the fields differ
+    Strategy {
+        path: String,
+        expected: Option<Strategy>,
+        actual: Option<Strategy>,
+    },
+}
+
+impl std::fmt::Display for SchemaDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingField { path } => write!(f, "{path}: missing field"),
+            Self::ExtraField { path } => write!(f, "{path}: extra field"),
+            Self::Name {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{path}: name differs (expected {expected:?}, actual {actual:?})"
+            ),
+            Self::DataType {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{path}: data type differs (expected {expected}, actual {actual})"
+            ),
+            Self::Nullability {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{path}: nullability differs (expected {}, actual {})",
+                display_nullable(*expected),
+                display_nullable(*actual),
+            ),
+            Self::Strategy {
+                path,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{path}: strategy differs (expected {}, actual {})",
+                display_strategy(expected),
+                display_strategy(actual),
+            ),
+        }
+    }
+}
+
+/// The differences between two schemas, see [`diff_schemas`]
+///
+/// The `Display` implementation lists one difference per line.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDiff {
+    pub differences: Vec<SchemaDifference>,
+}
+
+impl SchemaDiff {
+    /// Check whether the schemas are identical
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+impl std::fmt::Display for SchemaDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, difference) in self.differences.iter().enumerate() {
+            if idx != 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{difference}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Determine the differences between an expected and an actual schema
+///
+/// Fields of structs are matched by name, all other children by position. The
+/// children of fields with differing data types are not compared.
+///
+/// ```rust
+/// # use serde_arrow::schema::{diff_schemas, GenericDataType, GenericField, SerdeArrowSchema};
+/// let expected = SerdeArrowSchema::new()
+///     .with_field(GenericField::new("a", GenericDataType::U32, false))
+///     .with_field(GenericField::new("b", GenericDataType::LargeUtf8, false));
+/// let actual = SerdeArrowSchema::new()
+///     .with_field(GenericField::new("a", GenericDataType::I64, true));
+///
+/// let diff = diff_schemas(&expected, &actual);
+/// assert_eq!(
+///     diff.to_string(),
+///     [
+///         "$.a: data type differs (expected U32, actual I64)",
+///         "$.a: nullability differs (expected non-nullable, actual nullable)",
+///         "$.b: missing field",
+///     ]
+///     .join("\n"),
+/// );
+/// ```
+///
+pub fn diff_schemas(expected: &SerdeArrowSchema, actual: &SerdeArrowSchema) -> SchemaDiff {
+    let mut differences = Vec::new();
+    diff_named_children("$", &expected.fields, &actual.fields, &mut differences);
+    SchemaDiff { differences }
+}
+
+/// Determine the differences between two fields
+///
+pub fn diff_fields(path: &str, expected: &GenericField, actual: &GenericField) -> SchemaDiff {
+    let mut differences = Vec::new();
+    diff_field(path, expected, actual, &mut differences);
+    SchemaDiff { differences }
+}
+
+fn diff_field(
+    path: &str,
+    expected: &GenericField,
+    actual: &GenericField,
+    differences: &mut Vec<SchemaDifference>,
+) {
+    if expected.data_type != actual.data_type {
+        differences.push(SchemaDifference::DataType {
+            path: path.to_string(),
+            expected: expected.data_type,
+            actual: actual.data_type,
+        });
+    }
+    if expected.nullable != actual.nullable {
+        differences.push(SchemaDifference::Nullability {
+            path: path.to_string(),
+            expected: expected.nullable,
+            actual: actual.nullable,
+        });
+    }
+    if expected.strategy != actual.strategy {
+        differences.push(SchemaDifference::Strategy {
+            path: path.to_string(),
+            expected: expected.strategy.clone(),
+            actual: actual.strategy.clone(),
+        });
+    }
+    if expected.data_type != actual.data_type {
+        return;
+    }
+
+    let is_named = expected.data_type == GenericDataType::Struct
+        && !matches!(expected.strategy, Some(Strategy::TupleAsStruct))
+        && !matches!(actual.strategy, Some(Strategy::TupleAsStruct));
+    if is_named {
+        diff_named_children(path, &expected.children, &actual.children, differences);
+    } else {
+        diff_positional_children(path, &expected.children, &actual.children, differences);
+    }
+}
+
+fn diff_named_children(
+    path: &str,
+    expected: &[GenericField],
+    actual: &[GenericField],
+    differences: &mut Vec<SchemaDifference>,
+) {
+    for expected_child in expected {
+        let child_path = format!("{path}.{}", expected_child.name);
+        match actual.iter().find(|f| f.name == expected_child.name) {
+            Some(actual_child) => {
+                diff_field(&child_path, expected_child, actual_child, differences)
+            }
+            None => differences.push(SchemaDifference::MissingField { path: child_path }),
+        }
+    }
+    for actual_child in actual {
+        if !expected.iter().any(|f| f.name == actual_child.name) {
+            differences.push(SchemaDifference::ExtraField {
+                path: format!("{path}.{}", actual_child.name),
+            });
+        }
+    }
+}
+
+fn diff_positional_children(
+    path: &str,
+    expected: &[GenericField],
+    actual: &[GenericField],
+    differences: &mut Vec<SchemaDifference>,
+) {
+    for idx in 0..expected.len().max(actual.len()) {
+        match (expected.get(idx), actual.get(idx)) {
+            (Some(expected_child), Some(actual_child)) => {
+                let child_path = format!("{path}.{}", expected_child.name);
+                if expected_child.name != actual_child.name {
+                    differences.push(SchemaDifference::Name {
+                        path: child_path.clone(),
+                        expected: expected_child.name.clone(),
+                        actual: actual_child.name.clone(),
+                    });
+                }
+                diff_field(&child_path, expected_child, actual_child, differences);
+            }
+            (Some(expected_child), None) => differences.push(SchemaDifference::MissingField {
+                path: format!("{path}.{}", expected_child.name),
+            }),
+            (None, Some(actual_child)) => differences.push(SchemaDifference::ExtraField {
+                path: format!("{path}.{}", actual_child.name),
+            }),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+fn display_nullable(nullable: bool) -> &'static str {
+    if nullable {
+        "nullable"
+    } else {
+        "non-nullable"
+    }
+}
+
+fn display_strategy(strategy: &Option<Strategy>) -> String {
+    match strategy {
+        Some(strategy) => strategy.to_string(),
+        None => String::from("none"),
+    }
+}
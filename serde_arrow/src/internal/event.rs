@@ -1,6 +1,8 @@
 //! The underlying data format used to interact with serde
 //!
 
+use std::borrow::Cow;
+
 use crate::internal::error::{fail, Error, Result};
 
 /// The events used to interact with serde
@@ -290,6 +292,28 @@ impl<'a> Event<'a> {
     pub fn is_marker(&self) -> bool {
         matches!(self, Event::Item | Event::Some | Event::Variant(_, _))
     }
+
+    /// Format the event as the key of a map that is serialized as a struct
+    ///
+    /// Strings are used as is, booleans and integers are formatted. For all
+    /// other events `None` is returned.
+    ///
+    pub(crate) fn to_key(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Event::Str(val) => Some(Cow::Borrowed(val)),
+            Event::OwnedStr(val) => Some(Cow::Borrowed(val)),
+            Event::Bool(val) => Some(Cow::Owned(val.to_string())),
+            Event::I8(val) => Some(Cow::Owned(val.to_string())),
+            Event::I16(val) => Some(Cow::Owned(val.to_string())),
+            Event::I32(val) => Some(Cow::Owned(val.to_string())),
+            Event::I64(val) => Some(Cow::Owned(val.to_string())),
+            Event::U8(val) => Some(Cow::Owned(val.to_string())),
+            Event::U16(val) => Some(Cow::Owned(val.to_string())),
+            Event::U32(val) => Some(Cow::Owned(val.to_string())),
+            Event::U64(val) => Some(Cow::Owned(val.to_string())),
+            _ => None,
+        }
+    }
 }
 
 macro_rules! event_implement_simple_from {
@@ -1,6 +1,7 @@
-use chrono::{NaiveDateTime, TimeZone, Utc};
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
 
 use crate::internal::{
+    conversions::UNIX_EPOCH_DAYS_FROM_CE,
     error::{error, fail, Result},
     event::Event,
     source::{DynamicSource, EventSource, PeekableEventSource},
@@ -320,15 +321,22 @@ pub struct UnionSource<'a> {
     sources: Vec<DynamicSource<'a>>,
     names: Vec<&'a str>,
     types: Vec<u8>,
+    untagged: bool,
 }
 
 impl<'a> UnionSource<'a> {
-    pub fn new(names: Vec<&'a str>, sources: Vec<DynamicSource<'a>>, types: Vec<u8>) -> Self {
+    pub fn new(
+        names: Vec<&'a str>,
+        sources: Vec<DynamicSource<'a>>,
+        types: Vec<u8>,
+        untagged: bool,
+    ) -> Self {
         Self {
             next: UnionSourceState::Start { offset: 0 },
             sources,
             names,
             types,
+            untagged,
         }
     }
 }
@@ -346,6 +354,16 @@ impl<'a> EventSource<'a> for UnionSource<'a> {
                 }
 
                 let variant = self.types[offset] as usize;
+                if self.untagged {
+                    // NOTE: untagged unions emit the value without variant
+                    self.next = S::Value {
+                        offset,
+                        variant,
+                        depth: 0,
+                    };
+                    return self.next();
+                }
+
                 res = E::Variant(self.names[variant], variant);
                 S::Value {
                     offset,
@@ -589,6 +607,23 @@ fn consume_value<'a, S: EventSource<'a>>(source: &mut S) -> Result<()> {
     fail!("Could not consume value");
 }
 
+pub struct NaiveDateStrSource<S>(pub S);
+
+impl<'a, S: EventSource<'a>> EventSource<'a> for NaiveDateStrSource<S> {
+    fn next(&mut self) -> Result<Option<Event<'a>>> {
+        match self.0.next()? {
+            Some(Event::I32(val)) => {
+                let Some(val) = NaiveDate::from_num_days_from_ce_opt(val + UNIX_EPOCH_DAYS_FROM_CE)
+                else {
+                    fail!("Date32 value {val} is out of range");
+                };
+                Ok(Some(val.to_string().into()))
+            }
+            ev => Ok(ev),
+        }
+    }
+}
+
 pub struct NaiveDateTimeStrSource<S>(pub S);
 
 impl<'a, S: EventSource<'a>> EventSource<'a> for NaiveDateTimeStrSource<S> {
@@ -0,0 +1,461 @@
+//! Conversion between schemas and JSON Schema documents
+//!
+use serde_json::{json, Map, Value};
+
+use crate::internal::{
+    error::{fail, Result},
+    schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy},
+};
+
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// The maximum nesting depth when resolving `$ref` definitions
+const MAX_DEPTH: usize = 64;
+
+impl SerdeArrowSchema {
+    /// Describe records of this schema as a JSON Schema document
+    ///
+    /// Structs are described as objects, lists as arrays, maps as objects with
+    /// `additionalProperties`, and unions via `oneOf` following the externally
+    /// tagged representation of serde. Nullable fields allow `null` values and
+    /// are not required. Numeric types and dates are described with `format`
+    /// annotations (e.g., `"int32"`, `"double"`, `"date-time"`) to allow a
+    /// round trip with [`SerdeArrowSchema::from_json_schema`].
+    ///
+    /// Requires the `json` feature.
+    ///
+    pub fn to_json_schema(&self) -> Result<Value> {
+        self.validate()?;
+
+        let mut schema = struct_to_json_schema(&self.fields)?;
+        if let Value::Object(schema) = &mut schema {
+            schema.insert(String::from("$schema"), Value::from(DRAFT));
+        }
+        Ok(schema)
+    }
+
+    /// Build a schema from a JSON Schema document describing the records
+    ///
+    /// The document must describe an object. Objects with `properties` are
+    /// mapped to structs, objects with only `additionalProperties` to maps,
+    /// arrays to lists, arrays with `prefixItems` to tuples and `oneOf` /
+    /// `anyOf` alternatives to unions. Alternatives of the form `{"const":
+    /// "Variant"}` or objects with a single required property are interpreted
+    /// as the variants of an externally tagged enum, all other alternatives as
+    /// untagged unions. Fields are nullable if they allow `null` or are not
+    /// required. Strings with `format: "date-time"` are mapped to `Date64`
+    /// fields with `Strategy::UtcStrAsDate64` and strings with `format:
+    /// "date"` to `Date32` fields with `Strategy::NaiveStrAsDate32`. Local
+    /// references (`#/$defs/...` or `#/definitions/...`) are resolved.
+    ///
+    /// The order of the fields follows the order of the properties in the
+    /// parsed document. Note that `serde_json` sorts object keys unless its
+    /// `preserve_order` feature is enabled.
+    ///
+    /// Requires the `json` feature.
+    ///
+    pub fn from_json_schema(schema: &Value) -> Result<Self> {
+        let importer = Importer { root: schema };
+        let field = importer.field("$", "", schema, 0)?;
+        if field.data_type != GenericDataType::Struct {
+            fail!(
+                "Cannot import JSON Schema: the root must describe an object, found {}",
+                field.data_type
+            );
+        }
+
+        let result = Self {
+            fields: field.children,
+        };
+        result.validate()?;
+        Ok(result)
+    }
+}
+
+fn struct_to_json_schema(fields: &[GenericField]) -> Result<Value> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    for field in fields {
+        properties.insert(field.name.clone(), field_to_json_schema(field)?);
+        if !field.nullable {
+            required.push(Value::from(field.name.as_str()));
+        }
+    }
+
+    Ok(json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    }))
+}
+
+fn field_to_json_schema(field: &GenericField) -> Result<Value> {
+    let schema = data_type_to_json_schema(field)?;
+    if field.nullable && field.data_type != GenericDataType::Null {
+        Ok(into_nullable(schema))
+    } else {
+        Ok(schema)
+    }
+}
+
+fn into_nullable(mut schema: Value) -> Value {
+    if let Some(Value::String(ty)) = schema.get("type") {
+        let ty = ty.clone();
+        schema["type"] = json!([ty, "null"]);
+        schema
+    } else {
+        json!({"anyOf": [schema, {"type": "null"}]})
+    }
+}
+
+fn data_type_to_json_schema(field: &GenericField) -> Result<Value> {
+    use GenericDataType as D;
+
+    let schema = match field.data_type {
+        D::Null => json!({"type": "null"}),
+        D::Bool => json!({"type": "boolean"}),
+        D::I8 => json!({"type": "integer", "format": "int8"}),
+        D::I16 => json!({"type": "integer", "format": "int16"}),
+        D::I32 => json!({"type": "integer", "format": "int32"}),
+        D::I64 => json!({"type": "integer", "format": "int64"}),
+        D::U8 => json!({"type": "integer", "format": "uint8"}),
+        D::U16 => json!({"type": "integer", "format": "uint16"}),
+        D::U32 => json!({"type": "integer", "format": "uint32"}),
+        D::U64 => json!({"type": "integer", "format": "uint64"}),
+        D::F16 => json!({"type": "number", "format": "half"}),
+        D::F32 => json!({"type": "number", "format": "float"}),
+        D::F64 => json!({"type": "number", "format": "double"}),
+        D::Utf8 | D::LargeUtf8 => json!({"type": "string"}),
+        D::Date32 => match field.strategy {
+            Some(Strategy::NaiveStrAsDate32) => json!({"type": "string", "format": "date"}),
+            _ => json!({"type": "integer", "format": "date32"}),
+        },
+        D::Date64 => match field.strategy {
+            Some(Strategy::UtcStrAsDate64) => json!({"type": "string", "format": "date-time"}),
+            Some(Strategy::NaiveStrAsDate64) => {
+                json!({"type": "string", "format": "naive-date-time"})
+            }
+            _ => json!({"type": "integer", "format": "date64"}),
+        },
+        D::Struct if matches!(field.strategy, Some(Strategy::TupleAsStruct)) => {
+            let mut items = Vec::new();
+            for child in &field.children {
+                items.push(field_to_json_schema(child)?);
+            }
+            json!({
+                "type": "array",
+                "prefixItems": items,
+                "minItems": field.children.len(),
+                "maxItems": field.children.len(),
+            })
+        }
+        D::Struct => struct_to_json_schema(&field.children)?,
+        D::List | D::LargeList => json!({
+            "type": "array",
+            "items": field_to_json_schema(&field.children[0])?,
+        }),
+        D::Map => {
+            let entries = &field.children[0];
+            json!({
+                "type": "object",
+                "additionalProperties": field_to_json_schema(&entries.children[1])?,
+            })
+        }
+        D::Dictionary => data_type_to_json_schema(&field.children[1])?,
+        D::Union if matches!(field.strategy, Some(Strategy::UntaggedUnion)) => {
+            let mut alternatives = Vec::new();
+            for child in &field.children {
+                alternatives.push(field_to_json_schema(child)?);
+            }
+            json!({"oneOf": alternatives})
+        }
+        D::Union => {
+            let mut alternatives = Vec::new();
+            for child in &field.children {
+                if child.data_type == D::Null {
+                    alternatives.push(json!({"const": child.name}));
+                } else {
+                    alternatives.push(json!({
+                        "type": "object",
+                        "properties": {child.name.as_str(): field_to_json_schema(child)?},
+                        "required": [child.name],
+                        "additionalProperties": false,
+                    }));
+                }
+            }
+            json!({"oneOf": alternatives})
+        }
+    };
+    Ok(schema)
+}
+
+struct Importer<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Importer<'a> {
+    fn resolve(&self, path: &str, schema: &'a Value) -> Result<&'a Value> {
+        let Some(reference) = schema.get("$ref") else {
+            return Ok(schema);
+        };
+        let Some(reference) = reference.as_str() else {
+            fail!("Cannot import JSON Schema at {path}: $ref must be a string");
+        };
+        let Some(pointer) = reference.strip_prefix('#') else {
+            fail!("Cannot import JSON Schema at {path}: only local references are supported, found {reference}");
+        };
+        let Some(target) = self.root.pointer(pointer) else {
+            fail!("Cannot import JSON Schema at {path}: unknown reference {reference}");
+        };
+        Ok(target)
+    }
+
+    fn field(
+        &self,
+        path: &str,
+        name: &str,
+        schema: &'a Value,
+        depth: usize,
+    ) -> Result<GenericField> {
+        if depth > MAX_DEPTH {
+            fail!("Cannot import JSON Schema at {path}: maximum depth exceeded, recursive definitions are not supported");
+        }
+        let schema = self.resolve(path, schema)?;
+        let Some(object) = schema.as_object() else {
+            fail!("Cannot import JSON Schema at {path}: expected an object");
+        };
+
+        for key in ["oneOf", "anyOf"] {
+            if let Some(alternatives) = object.get(key) {
+                let Some(alternatives) = alternatives.as_array() else {
+                    fail!("Cannot import JSON Schema at {path}: {key} must be an array");
+                };
+                return self.alternatives(path, name, alternatives, depth);
+            }
+        }
+
+        let (ty, nullable) = match object.get("type") {
+            Some(Value::String(ty)) => (ty.as_str(), false),
+            Some(Value::Array(types)) => {
+                let nullable = types.iter().any(|ty| ty == "null");
+                let types = types.iter().filter(|ty| *ty != "null").collect::<Vec<_>>();
+                match types.as_slice() {
+                    [] => ("null", true),
+                    [Value::String(ty)] => (ty.as_str(), nullable),
+                    _ => fail!("Cannot import JSON Schema at {path}: multiple non-null types are not supported"),
+                }
+            }
+            Some(_) => {
+                fail!("Cannot import JSON Schema at {path}: type must be a string or an array")
+            }
+            None if object.contains_key("properties") => ("object", false),
+            None if object.contains_key("items") || object.contains_key("prefixItems") => {
+                ("array", false)
+            }
+            None if matches!(object.get("const"), Some(Value::String(_))) => ("string", false),
+            None if object.contains_key("enum") => ("string", false),
+            None => fail!("Cannot import JSON Schema at {path}: cannot determine the type"),
+        };
+        let format = object.get("format").and_then(Value::as_str);
+
+        let mut field = match (ty, format) {
+            ("null", _) => GenericField::new(name, GenericDataType::Null, true),
+            ("boolean", _) => GenericField::new(name, GenericDataType::Bool, false),
+            ("integer", format) => {
+                let data_type = match format {
+                    Some("int8") => GenericDataType::I8,
+                    Some("int16") => GenericDataType::I16,
+                    Some("int32") => GenericDataType::I32,
+                    Some("uint8") => GenericDataType::U8,
+                    Some("uint16") => GenericDataType::U16,
+                    Some("uint32") => GenericDataType::U32,
+                    Some("uint64") => GenericDataType::U64,
+                    Some("date32") => GenericDataType::Date32,
+                    Some("date64") => GenericDataType::Date64,
+                    _ => GenericDataType::I64,
+                };
+                GenericField::new(name, data_type, false)
+            }
+            ("number", format) => {
+                let data_type = match format {
+                    Some("half") => GenericDataType::F16,
+                    Some("float") => GenericDataType::F32,
+                    _ => GenericDataType::F64,
+                };
+                GenericField::new(name, data_type, false)
+            }
+            ("string", Some("date-time")) => {
+                GenericField::new(name, GenericDataType::Date64, false)
+                    .with_strategy(Strategy::UtcStrAsDate64)
+            }
+            ("string", Some("naive-date-time")) => {
+                GenericField::new(name, GenericDataType::Date64, false)
+                    .with_strategy(Strategy::NaiveStrAsDate64)
+            }
+            ("string", Some("date")) => GenericField::new(name, GenericDataType::Date32, false)
+                .with_strategy(Strategy::NaiveStrAsDate32),
+            ("string", _) => GenericField::new(name, GenericDataType::LargeUtf8, false),
+            ("array", _) => self.array(path, name, object, depth)?,
+            ("object", _) => self.object(path, name, object, depth)?,
+            (ty, _) => fail!("Cannot import JSON Schema at {path}: unsupported type {ty}"),
+        };
+        field.nullable |= nullable;
+        Ok(field)
+    }
+
+    fn array(
+        &self,
+        path: &str,
+        name: &str,
+        object: &'a Map<String, Value>,
+        depth: usize,
+    ) -> Result<GenericField> {
+        if let Some(items) = object.get("prefixItems") {
+            let Some(items) = items.as_array() else {
+                fail!("Cannot import JSON Schema at {path}: prefixItems must be an array");
+            };
+            let mut field = GenericField::new(name, GenericDataType::Struct, false)
+                .with_strategy(Strategy::TupleAsStruct);
+            for (idx, item) in items.iter().enumerate() {
+                let child_path = format!("{path}.{idx}");
+                let child = self.field(&child_path, &idx.to_string(), item, depth + 1)?;
+                field.children.push(child);
+            }
+            return Ok(field);
+        }
+
+        let Some(items) = object.get("items") else {
+            fail!("Cannot import JSON Schema at {path}: arrays require items");
+        };
+        let item_path = format!("{path}.element");
+        let item = self.field(&item_path, "element", items, depth + 1)?;
+        Ok(GenericField::new(name, GenericDataType::LargeList, false).with_child(item))
+    }
+
+    fn object(
+        &self,
+        path: &str,
+        name: &str,
+        object: &'a Map<String, Value>,
+        depth: usize,
+    ) -> Result<GenericField> {
+        let properties = object.get("properties").and_then(Value::as_object);
+        let additional = object
+            .get("additionalProperties")
+            .filter(|value| value.is_object());
+
+        match (properties, additional) {
+            (None, Some(values)) => {
+                let value_path = format!("{path}.$value");
+                let value = self.field(&value_path, "value", values, depth + 1)?;
+                let entries = GenericField::new("entries", GenericDataType::Struct, false)
+                    .with_child(GenericField::new("key", GenericDataType::LargeUtf8, false))
+                    .with_child(value);
+                Ok(GenericField::new(name, GenericDataType::Map, false).with_child(entries))
+            }
+            (properties, _) => {
+                let required = object
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default();
+
+                let mut field = GenericField::new(name, GenericDataType::Struct, false);
+                for (key, value) in properties.into_iter().flatten() {
+                    let child_path = format!("{path}.{key}");
+                    let mut child = self.field(&child_path, key, value, depth + 1)?;
+                    child.nullable |= !required.iter().any(|name| name == key);
+                    field.children.push(child);
+                }
+                Ok(field)
+            }
+        }
+    }
+
+    fn alternatives(
+        &self,
+        path: &str,
+        name: &str,
+        alternatives: &'a [Value],
+        depth: usize,
+    ) -> Result<GenericField> {
+        let mut nullable = false;
+        let mut candidates = Vec::new();
+        for alternative in alternatives {
+            let alternative = self.resolve(path, alternative)?;
+            if is_null_schema(alternative) {
+                nullable = true;
+            } else {
+                candidates.push(alternative);
+            }
+        }
+
+        let mut field = match candidates.as_slice() {
+            [] => GenericField::new(name, GenericDataType::Null, true),
+            [candidate] => self.field(path, name, candidate, depth + 1)?,
+            candidates if candidates.iter().all(|c| variant_name(c).is_some()) => {
+                if nullable {
+                    fail!("Cannot import JSON Schema at {path}: unions cannot be nullable");
+                }
+                let mut field = GenericField::new(name, GenericDataType::Union, false);
+                for candidate in candidates {
+                    let (variant, content) = variant_name(candidate).unwrap();
+                    let variant_path = format!("{path}.{variant}");
+                    let child = match content {
+                        Some(content) => self.field(&variant_path, variant, content, depth + 1)?,
+                        None => GenericField::new(variant, GenericDataType::Null, true),
+                    };
+                    field.children.push(child);
+                }
+                field
+            }
+            candidates => {
+                if nullable {
+                    fail!(
+                        "Cannot import JSON Schema at {path}: untagged unions cannot be nullable"
+                    );
+                }
+                let mut field = GenericField::new(name, GenericDataType::Union, false)
+                    .with_strategy(Strategy::UntaggedUnion);
+                for (idx, candidate) in candidates.iter().enumerate() {
+                    let child_path = format!("{path}.{idx}");
+                    let child = self.field(&child_path, &idx.to_string(), candidate, depth + 1)?;
+                    field.children.push(child);
+                }
+                field
+            }
+        };
+        field.nullable |= nullable;
+        Ok(field)
+    }
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema
+        .get("type")
+        .map(|ty| ty == "null")
+        .unwrap_or_default()
+}
+
+/// Interpret a schema as an externally tagged enum variant
+///
+/// Unit variants are described as `{"const": "Variant"}`, variants with
+/// content as objects with a single required property.
+///
+fn variant_name(schema: &Value) -> Option<(&str, Option<&Value>)> {
+    if let Some(Value::String(variant)) = schema.get("const") {
+        return Some((variant.as_str(), None));
+    }
+
+    let properties = schema.get("properties")?.as_object()?;
+    let required = schema.get("required")?.as_array()?;
+    if properties.len() != 1 || required.len() != 1 {
+        return None;
+    }
+    let (variant, content) = properties.iter().next()?;
+    if required[0] != *variant {
+        return None;
+    }
+    Some((variant.as_str(), Some(content)))
+}
@@ -0,0 +1,322 @@
+//! Merge fields traced from different sources into a single schema
+//!
+use crate::internal::{
+    error::{fail, Result},
+    schema::{coerce_numeric_types, GenericDataType, GenericField, Strategy, UntaggedShape},
+};
+
+/// Merge multiple lists of fields into a single list of fields
+///
+/// Fields are matched by name. Fields missing in any of the lists are marked as
+/// nullable.
+///
+pub fn merge_fields(schemas: &[Vec<GenericField>]) -> Result<Vec<GenericField>> {
+    let Some((first, rest)) = schemas.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    let mut result = first.clone();
+    for fields in rest {
+        result = merge_named_children("$", &result, fields)?;
+    }
+
+    for field in &result {
+        field.validate()?;
+    }
+    Ok(result)
+}
+
+fn merge_named_children(
+    path: &str,
+    left: &[GenericField],
+    right: &[GenericField],
+) -> Result<Vec<GenericField>> {
+    let mut result = Vec::new();
+
+    for left_field in left {
+        if let Some(right_field) = right.iter().find(|f| f.name == left_field.name) {
+            result.push(merge_field(path, left_field, right_field)?);
+        } else {
+            result.push(into_nullable(left_field.clone()));
+        }
+    }
+
+    for right_field in right {
+        if !left.iter().any(|f| f.name == right_field.name) {
+            result.push(into_nullable(right_field.clone()));
+        }
+    }
+
+    Ok(result)
+}
+
+fn merge_positional_children(
+    path: &str,
+    left: &[GenericField],
+    right: &[GenericField],
+) -> Result<Vec<GenericField>> {
+    if left.len() != right.len() {
+        fail!(
+            "Cannot merge fields at {path}: differing number of elements {} and {}",
+            left.len(),
+            right.len()
+        );
+    }
+
+    let mut result = Vec::new();
+    for (left_field, right_field) in left.iter().zip(right) {
+        result.push(merge_field(path, left_field, right_field)?);
+    }
+    Ok(result)
+}
+
+fn merge_field(path: &str, left: &GenericField, right: &GenericField) -> Result<GenericField> {
+    type D = GenericDataType;
+
+    let path = format!("{path}.{name}", name = left.name);
+    let nullable = left.nullable || right.nullable;
+
+    if is_untagged_union(left) || is_untagged_union(right) {
+        return merge_untagged_union(&path, left, right);
+    }
+
+    if left.data_type == D::Null && left.strategy.is_none() {
+        return Ok(with_name(into_nullable(right.clone()), &left.name));
+    }
+    if right.data_type == D::Null && right.strategy.is_none() {
+        return Ok(into_nullable(left.clone()));
+    }
+
+    if left.data_type == right.data_type {
+        if left.strategy != right.strategy {
+            fail!(
+                "Cannot merge fields at {path}: incompatible strategies {} and {}",
+                display_strategy(&left.strategy),
+                display_strategy(&right.strategy),
+            );
+        }
+
+        let mut field = left.clone();
+        field.nullable = nullable;
+        field.children = match left.data_type {
+            D::Struct if left.strategy == Some(Strategy::TupleAsStruct) => {
+                merge_positional_children(&path, &left.children, &right.children)?
+            }
+            D::Struct => merge_named_children(&path, &left.children, &right.children)?,
+            D::List | D::LargeList | D::Map | D::Dictionary => {
+                merge_positional_children(&path, &left.children, &right.children)?
+            }
+            D::Union => merge_union_variants(&path, &left.children, &right.children)?,
+            _ => left.children.clone(),
+        };
+        return Ok(field);
+    }
+
+    if let Some(data_type) = coerce_numeric_types(left.data_type, right.data_type) {
+        let mut field = left.clone();
+        field.data_type = data_type;
+        field.nullable = nullable;
+        field.strategy = merge_strategies(&path, &left.strategy, &right.strategy)?;
+
+        // NOTE: integers are only accepted by float fields, if values are coerced
+        if is_float(data_type) && (!is_float(left.data_type) || !is_float(right.data_type)) {
+            field.strategy = field.strategy.or(Some(Strategy::InconsistentTypes));
+        }
+        return Ok(field);
+    }
+
+    if matches!(
+        (left.data_type, right.data_type),
+        (D::List, D::LargeList) | (D::LargeList, D::List)
+    ) {
+        let mut field = GenericField::new(&left.name, D::LargeList, nullable);
+        field.children = merge_positional_children(&path, &left.children, &right.children)?;
+        return Ok(field);
+    }
+
+    if is_string(left) && is_string(right) {
+        // NOTE: strings serialized as dates can be stored as strings, but not vice versa
+        let mut field = GenericField::new(&left.name, D::LargeUtf8, nullable);
+        if is_formatted(left) || is_formatted(right) {
+            field.strategy = Some(Strategy::InconsistentTypes);
+        }
+        return Ok(field);
+    }
+
+    if let Some(wider) = widen(left, right).or_else(|| widen(right, left)) {
+        let mut field = with_name(wider.clone(), &left.name);
+        field.nullable = nullable;
+        return Ok(field);
+    }
+
+    fail!(
+        "Cannot merge fields at {path}: incompatible types {} and {}",
+        left.data_type,
+        right.data_type,
+    );
+}
+
+/// Merge the variants of tagged unions by their index
+///
+fn merge_union_variants(
+    path: &str,
+    left: &[GenericField],
+    right: &[GenericField],
+) -> Result<Vec<GenericField>> {
+    let mut result = Vec::new();
+
+    for idx in 0..left.len().max(right.len()) {
+        let variant = match (left.get(idx), right.get(idx)) {
+            (Some(left), Some(right)) if is_unknown_variant(left) => right.clone(),
+            (Some(left), Some(right)) if is_unknown_variant(right) => left.clone(),
+            (Some(left), Some(right)) => {
+                if left.name != right.name {
+                    fail!(
+                        "Cannot merge fields at {path}: incompatible names for variant {idx}: {} and {}",
+                        left.name,
+                        right.name,
+                    );
+                }
+                merge_field(path, left, right)?
+            }
+            (Some(variant), None) | (None, Some(variant)) => variant.clone(),
+            (None, None) => unreachable!(),
+        };
+        result.push(variant);
+    }
+
+    Ok(result)
+}
+
+/// Merge fields, where at least one is an untagged union, by their shapes
+///
+fn merge_untagged_union(
+    path: &str,
+    left: &GenericField,
+    right: &GenericField,
+) -> Result<GenericField> {
+    let mut nullable = false;
+    let mut children: Vec<(UntaggedShape, GenericField)> = Vec::new();
+
+    for field in [left, right] {
+        let candidates = if is_untagged_union(field) {
+            nullable |= field.nullable;
+            field.children.as_slice()
+        } else {
+            std::slice::from_ref(field)
+        };
+
+        for child in candidates {
+            let Some(shape) = UntaggedShape::from_field(child) else {
+                // null values of an untagged union are tracked via its nullability
+                nullable = true;
+                continue;
+            };
+            nullable |= child.nullable;
+
+            if let Some(pos) = children.iter().position(|(s, _)| *s == shape) {
+                let merged = merge_field(path, &children[pos].1, child)?;
+                children[pos].1 = merged;
+            } else {
+                children.push((shape, child.clone()));
+            }
+        }
+    }
+
+    if children.len() == 1 {
+        let (_, child) = children.remove(0);
+        let mut field = with_name(child, &left.name);
+        field.nullable |= nullable;
+        return Ok(field);
+    }
+    if nullable {
+        fail!("Cannot merge fields at {path}: untagged unions with multiple shapes cannot be nullable");
+    }
+
+    let mut field = GenericField::new(&left.name, GenericDataType::Union, false)
+        .with_strategy(Strategy::UntaggedUnion);
+    for (idx, (_, child)) in children.into_iter().enumerate() {
+        field.children.push(with_name(child, &idx.to_string()));
+    }
+    Ok(field)
+}
+
+/// Return the wider field, if `left` can be widened to `right`
+///
+/// Only the listed widenings are allowed. All values accepted by `left` must
+/// also be accepted by `right`.
+///
+fn widen<'a>(left: &GenericField, right: &'a GenericField) -> Option<&'a GenericField> {
+    type D = GenericDataType;
+
+    match (left.data_type, right.data_type) {
+        (D::Utf8 | D::LargeUtf8, D::Dictionary) if !is_formatted(left) || is_formatted(right) => {
+            Some(right)
+        }
+        _ => None,
+    }
+}
+
+fn merge_strategies(
+    path: &str,
+    left: &Option<Strategy>,
+    right: &Option<Strategy>,
+) -> Result<Option<Strategy>> {
+    match (left, right) {
+        (Some(left), Some(right)) if left != right => {
+            fail!("Cannot merge fields at {path}: incompatible strategies {left} and {right}",)
+        }
+        _ => Ok(left.clone().or_else(|| right.clone())),
+    }
+}
+
+fn is_float(data_type: GenericDataType) -> bool {
+    matches!(
+        data_type,
+        GenericDataType::F16 | GenericDataType::F32 | GenericDataType::F64
+    )
+}
+
+/// Whether the field formats primitive values as strings
+fn is_formatted(field: &GenericField) -> bool {
+    matches!(field.strategy, Some(Strategy::InconsistentTypes))
+}
+
+fn is_untagged_union(field: &GenericField) -> bool {
+    field.data_type == GenericDataType::Union
+        && matches!(field.strategy, Some(Strategy::UntaggedUnion))
+}
+
+fn is_string(field: &GenericField) -> bool {
+    match field.data_type {
+        GenericDataType::Utf8 | GenericDataType::LargeUtf8 => true,
+        GenericDataType::Date32 => matches!(field.strategy, Some(Strategy::NaiveStrAsDate32)),
+        GenericDataType::Date64 => matches!(
+            field.strategy,
+            Some(Strategy::NaiveStrAsDate64) | Some(Strategy::UtcStrAsDate64)
+        ),
+        _ => false,
+    }
+}
+
+fn is_unknown_variant(field: &GenericField) -> bool {
+    field.data_type == GenericDataType::Null
+        && matches!(field.strategy, Some(Strategy::UnknownVariant))
+}
+
+fn into_nullable(mut field: GenericField) -> GenericField {
+    field.nullable = true;
+    field
+}
+
+fn with_name(mut field: GenericField, name: &str) -> GenericField {
+    field.name = name.to_string();
+    field
+}
+
+fn display_strategy(strategy: &Option<Strategy>) -> String {
+    match strategy {
+        Some(strategy) => strategy.to_string(),
+        None => String::from("none"),
+    }
+}
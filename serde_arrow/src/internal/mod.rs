@@ -1,21 +1,29 @@
+pub(crate) mod codegen;
 pub(crate) mod conversions;
+pub(crate) mod diff;
 pub(crate) mod error;
 pub(crate) mod event;
 pub(crate) mod generic_sources;
+#[cfg(feature = "json")]
+pub(crate) mod json_schema;
+pub(crate) mod merge;
 pub(crate) mod schema;
 pub mod serialization;
 pub(crate) mod sink;
 pub(crate) mod source;
+pub(crate) mod type_tracing;
+pub(crate) mod typed_schema;
 
-use std::sync::RwLock;
+use std::sync::{Arc, RwLock};
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use self::{
     error::{fail, Result},
     schema::{GenericDataType, GenericField, Tracer, TracingOptions},
     serialization::{compile_serialization, CompilationOptions, Interpreter},
     sink::{serialize_into_sink, EventSerializer, EventSink, StripOuterSequenceSink},
+    type_tracing::trace_type,
 };
 
 pub static CONFIGURATION: RwLock<Configuration> = RwLock::new(Configuration {
@@ -53,37 +61,95 @@ pub fn serialize_into_fields<T>(items: &T, options: TracingOptions) -> Result<Ve
 where
     T: Serialize + ?Sized,
 {
-    let tracer = Tracer::new(String::from("$"), options);
-    let mut tracer = StripOuterSequenceSink::new(tracer);
+    let mut tracer = GenericSchemaTracer::new(options);
+    tracer.trace_batch(items)?;
+    tracer.get_fields()
+}
+
+pub fn serialize_into_field<T>(
+    items: &T,
+    name: &str,
+    options: TracingOptions,
+) -> Result<GenericField>
+where
+    T: Serialize + ?Sized,
+{
+    let tracer = Tracer::new(String::from("$"), Arc::new(options));
+    let tracer = StripOuterSequenceSink::new(tracer);
+    let mut tracer = tracer;
     serialize_into_sink(&mut tracer, items)?;
-    let root = tracer.into_inner().to_field("root")?;
+
+    let field = tracer.into_inner().to_field(name)?;
+    Ok(field)
+}
+
+pub fn serialize_into_fields_from_type<'de, T>(options: TracingOptions) -> Result<Vec<GenericField>>
+where
+    T: Deserialize<'de>,
+{
+    let root = serialize_into_field_from_type::<T>("root", options)?;
 
     match root.data_type {
         GenericDataType::Struct => {}
-        GenericDataType::Null => fail!("No records found to determine schema"),
         dt => fail!("Unexpected root data type {dt:?}"),
     };
 
     Ok(root.children)
 }
 
-pub fn serialize_into_field<T>(
-    items: &T,
+pub fn serialize_into_field_from_type<'de, T>(
     name: &str,
     options: TracingOptions,
 ) -> Result<GenericField>
 where
-    T: Serialize + ?Sized,
+    T: Deserialize<'de>,
 {
-    let tracer = Tracer::new(String::from("$"), options);
-    let tracer = StripOuterSequenceSink::new(tracer);
-    let mut tracer = tracer;
-    serialize_into_sink(&mut tracer, items)?;
+    // NOTE: the keys of maps are not known, therefore they cannot be traced as structs
+    let options = options.map_as_struct(false);
 
-    let field = tracer.into_inner().to_field(name)?;
+    let mut tracer = Tracer::new(String::from("$"), Arc::new(options));
+    trace_type::<T>(&mut tracer)?;
+
+    let field = tracer.to_field(name)?;
     Ok(field)
 }
 
+pub struct GenericSchemaTracer(pub Tracer);
+
+impl GenericSchemaTracer {
+    pub fn new(options: TracingOptions) -> Self {
+        Self(Tracer::new(String::from("$"), Arc::new(options)))
+    }
+
+    pub fn trace<T: Serialize + ?Sized>(&mut self, item: &T) -> Result<()> {
+        item.serialize(EventSerializer(&mut self.0))
+    }
+
+    pub fn trace_batch<T: Serialize + ?Sized>(&mut self, items: &T) -> Result<()> {
+        let placeholder = Tracer::new(String::from("$"), Arc::default());
+        let tracer = std::mem::replace(&mut self.0, placeholder);
+
+        let mut tracer = StripOuterSequenceSink::new(tracer);
+        let res = items.serialize(EventSerializer(&mut tracer));
+        self.0 = tracer.into_inner();
+
+        res
+    }
+
+    pub fn get_fields(&mut self) -> Result<Vec<GenericField>> {
+        self.0.finish()?;
+        let root = self.0.to_field("root")?;
+
+        match root.data_type {
+            GenericDataType::Struct => {}
+            GenericDataType::Null => fail!("No records found to determine schema"),
+            dt => fail!("Unexpected root data type {dt:?}"),
+        };
+
+        Ok(root.children)
+    }
+}
+
 pub struct GenericBuilder(pub Interpreter);
 
 impl GenericBuilder {
@@ -98,7 +164,14 @@ impl GenericBuilder {
     }
 
     pub fn new_for_arrays(fields: &[GenericField]) -> Result<Self> {
-        let program = compile_serialization(fields, CompilationOptions::default())?;
+        Self::new_for_arrays_with_options(fields, CompilationOptions::default())
+    }
+
+    pub fn new_for_arrays_with_options(
+        fields: &[GenericField],
+        options: CompilationOptions,
+    ) -> Result<Self> {
+        let program = compile_serialization(fields, options.wrap_with_struct(true))?;
         let interpreter = Interpreter::new(program);
 
         Ok(Self(interpreter))
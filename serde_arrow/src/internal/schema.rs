@@ -1,10 +1,14 @@
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     iter,
     str::FromStr,
+    sync::Arc,
 };
 
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 use crate::internal::{
+    diff::diff_fields,
     error::{fail, Error, Result},
     event::Event,
     sink::EventSink,
@@ -36,7 +40,10 @@ fn field_is_compatible(left: &GenericField, right: &GenericField) -> bool {
             &right.data_type,
             D::I32 | D::I64 | D::U8 | D::U16 | D::U32 | D::U64
         ),
-        D::I32 => matches!(&right.data_type, D::I64 | D::U8 | D::U16 | D::U32 | D::U64),
+        D::I32 => matches!(
+            &right.data_type,
+            D::I64 | D::U8 | D::U16 | D::U32 | D::U64 | D::Date32
+        ),
         D::I64 => matches!(
             &right.data_type,
             D::U8 | D::U16 | D::U32 | D::U64 | D::Date64
@@ -47,6 +54,7 @@ fn field_is_compatible(left: &GenericField, right: &GenericField) -> bool {
         D::Utf8 => match &right.data_type {
             D::LargeUtf8 => true,
             D::Dictionary => true,
+            D::Date32 => matches!(&right.strategy, Some(Strategy::NaiveStrAsDate32)),
             D::Date64 => matches!(
                 &right.strategy,
                 Some(Strategy::NaiveStrAsDate64) | Some(Strategy::UtcStrAsDate64)
@@ -55,6 +63,7 @@ fn field_is_compatible(left: &GenericField, right: &GenericField) -> bool {
         },
         D::LargeUtf8 => match &right.data_type {
             D::Dictionary => true,
+            D::Date32 => matches!(&right.strategy, Some(Strategy::NaiveStrAsDate32)),
             D::Date64 => matches!(
                 &right.strategy,
                 Some(Strategy::NaiveStrAsDate64) | Some(Strategy::UtcStrAsDate64)
@@ -83,6 +92,12 @@ pub const STRATEGY_KEY: &str = "SERDE_ARROW:strategy";
 #[non_exhaustive]
 pub enum Strategy {
     /// Marker that the type of the field could not be determined during tracing
+    /// or that the field was traced from values of differing types
+    ///
+    /// Numeric, string, and dictionary fields with this strategy accept
+    /// values of differing types in serialization. Numeric fields convert
+    /// integers, floats and booleans with range checks, string fields format
+    /// primitive values as strings.
     ///
     InconsistentTypes,
     /// Serialize Rust strings containing UTC datetimes with timezone as Arrows
@@ -93,6 +108,10 @@ pub enum Strategy {
     /// Date64
     ///
     NaiveStrAsDate64,
+    /// Serialize Rust strings containing dates without time (e.g.,
+    /// `"2023-01-31"`) as Arrow Date32
+    ///
+    NaiveStrAsDate32,
     /// Serialize Rust tuples as Arrow structs with numeric field names starting
     /// at `"0"`
     ///
@@ -100,6 +119,14 @@ pub enum Strategy {
     /// contain different types, whereas Arrow sequences must be of uniform type
     ///
     TupleAsStruct,
+    /// Serialize Rust tuples with elements of a single type as Arrow lists
+    ///
+    /// Each tuple is written as a list with one element per tuple entry. Arrow
+    /// lists require a uniform type, therefore this strategy only applies to
+    /// tuples whose elements all share the same type, e.g., coordinates like
+    /// `(f64, f64, f64)`.
+    ///
+    TupleAsList,
     /// Serialize Rust maps as Arrow structs
     ///
     /// The field names are sorted by name to ensure unordered map (e.g.,
@@ -122,6 +149,37 @@ pub enum Strategy {
     /// serialization or deseralization of such a field is attempted, it will
     /// result in an error.
     UnknownVariant,
+    /// Serialize untagged Rust enums as Arrow unions
+    ///
+    /// This strategy applies only to fields with DataType Union. Each child
+    /// corresponds to one of the shapes (e.g., a number, a string, a struct)
+    /// observed for the enum. In serialization, values are routed to the child
+    /// matching their shape. In deserialization, the values of the children are
+    /// emitted without variant information, as expected by
+    /// `#[serde(untagged)]` enums.
+    ///
+    /// The shapes of the children must be unique. Nullable untagged unions are
+    /// not supported.
+    ///
+    UntaggedUnion,
+    /// Serialize Rust strings containing numbers (e.g., `"42"` or `"1.5e3"`)
+    /// as Arrow integers or floats
+    ///
+    /// This strategy applies only to fields with numeric data types. The
+    /// strings are parsed into the type of the field with range checks. Fields
+    /// with this strategy also accept numbers, which are converted as with
+    /// `CompilationOptions::coerce_numbers`. In deserialization, the numbers
+    /// are emitted as numbers. They can be formatted as strings with
+    /// `DeserializationOptions::format_numbers_as_strings`.
+    ///
+    StrAsNumber,
+    /// Serialize Rust strings containing booleans (`"true"` or `"false"`) as
+    /// Arrow booleans
+    ///
+    /// This strategy applies only to fields with DataType Bool. Fields with
+    /// this strategy also accept booleans.
+    ///
+    StrAsBool,
 }
 
 impl std::fmt::Display for Strategy {
@@ -130,9 +188,14 @@ impl std::fmt::Display for Strategy {
             Self::InconsistentTypes => write!(f, "InconsistentTypes"),
             Self::UtcStrAsDate64 => write!(f, "UtcStrAsDate64"),
             Self::NaiveStrAsDate64 => write!(f, "NaiveStrAsDate64"),
+            Self::NaiveStrAsDate32 => write!(f, "NaiveStrAsDate32"),
             Self::TupleAsStruct => write!(f, "TupleAsStruct"),
+            Self::TupleAsList => write!(f, "TupleAsList"),
             Self::MapAsStruct => write!(f, "MapAsStruct"),
             Self::UnknownVariant => write!(f, "UnknownVariant"),
+            Self::UntaggedUnion => write!(f, "UntaggedUnion"),
+            Self::StrAsNumber => write!(f, "StrAsNumber"),
+            Self::StrAsBool => write!(f, "StrAsBool"),
         }
     }
 }
@@ -145,14 +208,33 @@ impl FromStr for Strategy {
             "InconsistentTypes" => Ok(Self::InconsistentTypes),
             "UtcStrAsDate64" => Ok(Self::UtcStrAsDate64),
             "NaiveStrAsDate64" => Ok(Self::NaiveStrAsDate64),
+            "NaiveStrAsDate32" => Ok(Self::NaiveStrAsDate32),
             "TupleAsStruct" => Ok(Self::TupleAsStruct),
+            "TupleAsList" => Ok(Self::TupleAsList),
             "MapAsStruct" => Ok(Self::MapAsStruct),
             "UnknownVariant" => Ok(Self::UnknownVariant),
+            "UntaggedUnion" => Ok(Self::UntaggedUnion),
+            "StrAsNumber" => Ok(Self::StrAsNumber),
+            "StrAsBool" => Ok(Self::StrAsBool),
             _ => fail!("Unknown strategy {s}"),
         }
     }
 }
 
+impl Serialize for Strategy {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Strategy {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(<D::Error as serde::de::Error>::custom)
+    }
+}
+
 impl From<Strategy> for BTreeMap<String, String> {
     fn from(value: Strategy) -> Self {
         let mut res = BTreeMap::new();
@@ -169,7 +251,14 @@ impl From<Strategy> for HashMap<String, String> {
     }
 }
 
+/// The data types supported by `serde_arrow`, independent of the arrow
+/// implementation
+///
+/// Additional data types may be added in future versions.
+///
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum GenericDataType {
     Null,
     Bool,
@@ -186,6 +275,7 @@ pub enum GenericDataType {
     F64,
     Utf8,
     LargeUtf8,
+    Date32,
     Date64,
     Struct,
     List,
@@ -214,6 +304,7 @@ impl std::fmt::Display for GenericDataType {
             F16 => write!(f, "F16"),
             F32 => write!(f, "F32"),
             F64 => write!(f, "F64"),
+            Date32 => write!(f, "Date32"),
             Date64 => write!(f, "Date64"),
             Struct => write!(f, "Struct"),
             List => write!(f, "List"),
@@ -225,12 +316,35 @@ impl std::fmt::Display for GenericDataType {
     }
 }
 
+/// A field description independent of the arrow implementation
+///
+/// Nested types store their children in `children`: structs their fields,
+/// lists their item, maps their entries, unions their variants and
+/// dictionaries their key and value types.
+///
+/// Fields are constructed with [`GenericField::new`], as additional attributes
+/// may be added in future versions.
+///
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub struct GenericField {
-    pub data_type: GenericDataType,
     pub name: String,
-    pub strategy: Option<Strategy>,
+    pub data_type: GenericDataType,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "std::ops::Not::not")
+    )]
     pub nullable: bool,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub strategy: Option<Strategy>,
+    #[cfg_attr(
+        feature = "json",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
     pub children: Vec<GenericField>,
 }
 
@@ -252,20 +366,21 @@ impl GenericField {
     pub fn validate(&self) -> Result<()> {
         match self.data_type {
             GenericDataType::Null => self.validate_null(),
-            GenericDataType::Bool => self.validate_primitive(),
-            GenericDataType::U8 => self.validate_primitive(),
-            GenericDataType::U16 => self.validate_primitive(),
-            GenericDataType::U32 => self.validate_primitive(),
-            GenericDataType::U64 => self.validate_primitive(),
-            GenericDataType::I8 => self.validate_primitive(),
-            GenericDataType::I16 => self.validate_primitive(),
-            GenericDataType::I32 => self.validate_primitive(),
-            GenericDataType::I64 => self.validate_primitive(),
-            GenericDataType::F16 => self.validate_primitive(),
-            GenericDataType::F32 => self.validate_primitive(),
-            GenericDataType::F64 => self.validate_primitive(),
-            GenericDataType::Utf8 => self.validate_primitive(),
-            GenericDataType::LargeUtf8 => self.validate_primitive(),
+            GenericDataType::Bool => self.validate_bool(),
+            GenericDataType::U8 => self.validate_number(),
+            GenericDataType::U16 => self.validate_number(),
+            GenericDataType::U32 => self.validate_number(),
+            GenericDataType::U64 => self.validate_number(),
+            GenericDataType::I8 => self.validate_number(),
+            GenericDataType::I16 => self.validate_number(),
+            GenericDataType::I32 => self.validate_number(),
+            GenericDataType::I64 => self.validate_number(),
+            GenericDataType::F16 => self.validate_number(),
+            GenericDataType::F32 => self.validate_number(),
+            GenericDataType::F64 => self.validate_number(),
+            GenericDataType::Utf8 => self.validate_utf8(),
+            GenericDataType::LargeUtf8 => self.validate_utf8(),
+            GenericDataType::Date32 => self.validate_date32(),
             GenericDataType::Date64 => self.validate_date64(),
             GenericDataType::Struct => self.validate_struct(),
             GenericDataType::Map => self.validate_map(),
@@ -289,7 +404,11 @@ impl GenericField {
             .map_err(|err| Error::custom_from(format!("invalid other field: {err}"), err))?;
 
         if !field_is_compatible(self, other) {
-            fail!("incompatible fields: {self:?}, {other:?}");
+            let diff = diff_fields("$", self, other);
+            if diff.is_empty() {
+                fail!("incompatible fields: {self:?}, {other:?}");
+            }
+            fail!("incompatible fields:\n{diff}");
         }
 
         Ok(())
@@ -306,6 +425,85 @@ impl GenericField {
     }
 }
 
+/// A schema, i.e., a list of top-level fields, independent of the arrow
+/// implementation
+///
+/// It can be converted to and from the fields of the `arrow` and `arrow2`
+/// crates, e.g., via `SerdeArrowSchema::to_arrow2_fields` or
+/// `SerdeArrowSchema::from_arrow2_fields`. Only the information used by
+/// `serde_arrow` is kept: field metadata other than the strategy is dropped
+/// and data types not supported by `serde_arrow` are rejected.
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct SerdeArrowSchema {
+    pub fields: Vec<GenericField>,
+}
+
+impl SerdeArrowSchema {
+    /// Construct a new empty schema
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a field to the schema
+    pub fn with_field(mut self, field: GenericField) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Determine the schema for the given items
+    ///
+    /// See `serialize_into_fields` of the `arrow` or `arrow2` modules for
+    /// details.
+    ///
+    pub fn from_samples<T: Serialize + ?Sized>(items: &T, options: TracingOptions) -> Result<Self> {
+        let fields = super::serialize_into_fields(items, options)?;
+        Ok(Self { fields })
+    }
+
+    /// Determine the schema from the given record type
+    ///
+    /// See `serialize_into_fields_from_type` of the `arrow` or `arrow2` modules
+    /// for details.
+    ///
+    pub fn from_type<'de, T: Deserialize<'de>>(options: TracingOptions) -> Result<Self> {
+        let fields = super::serialize_into_fields_from_type::<T>(options)?;
+        Ok(Self { fields })
+    }
+
+    /// Validate all fields of the schema
+    pub fn validate(&self) -> Result<()> {
+        for field in &self.fields {
+            field.validate()?;
+        }
+        Ok(())
+    }
+
+    /// Serialize the schema into a JSON string
+    ///
+    /// Strategies are stored as strings. Default values, i.e., non-nullable
+    /// fields, fields without strategy or without children, are omitted.
+    ///
+    /// Requires the `json` feature.
+    ///
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse and validate a schema from a JSON string
+    ///
+    /// Requires the `json` feature.
+    ///
+    #[cfg(feature = "json")]
+    pub fn from_json(s: &str) -> Result<Self> {
+        let schema: Self = serde_json::from_str(s)?;
+        schema.validate()?;
+        Ok(schema)
+    }
+}
+
 impl GenericField {
     pub(crate) fn validate_null(&self) -> Result<()> {
         if !matches!(
@@ -337,6 +535,48 @@ impl GenericField {
         Ok(())
     }
 
+    pub(crate) fn validate_bool(&self) -> Result<()> {
+        if matches!(self.strategy, Some(Strategy::StrAsBool)) {
+            return self.validate_primitive_children();
+        }
+        self.validate_primitive()
+    }
+
+    pub(crate) fn validate_number(&self) -> Result<()> {
+        if matches!(
+            self.strategy,
+            Some(Strategy::StrAsNumber) | Some(Strategy::InconsistentTypes)
+        ) {
+            return self.validate_primitive_children();
+        }
+        self.validate_primitive()
+    }
+
+    pub(crate) fn validate_utf8(&self) -> Result<()> {
+        if matches!(self.strategy, Some(Strategy::InconsistentTypes)) {
+            return self.validate_primitive_children();
+        }
+        self.validate_primitive()
+    }
+
+    fn validate_primitive_children(&self) -> Result<()> {
+        if !self.children.is_empty() {
+            fail!("{} field must not have children", self.data_type);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn validate_date32(&self) -> Result<()> {
+        if !matches!(self.strategy, None | Some(Strategy::NaiveStrAsDate32)) {
+            fail!(
+                "invalid strategy for Date32 field: {}",
+                self.strategy.as_ref().unwrap()
+            );
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn validate_date64(&self) -> Result<()> {
         if !matches!(
             self.strategy,
@@ -401,7 +641,7 @@ impl GenericField {
     }
 
     pub(crate) fn validate_list(&self) -> Result<()> {
-        if self.strategy.is_some() {
+        if !matches!(self.strategy, None | Some(Strategy::TupleAsList)) {
             fail!(
                 "invalid strategy for List field: {}",
                 self.strategy.as_ref().unwrap()
@@ -419,7 +659,7 @@ impl GenericField {
     }
 
     pub(crate) fn validate_union(&self) -> Result<()> {
-        if self.strategy.is_some() {
+        if !matches!(self.strategy, None | Some(Strategy::UntaggedUnion)) {
             fail!(
                 "invalid strategy for Union field: {}",
                 self.strategy.as_ref().unwrap()
@@ -431,11 +671,26 @@ impl GenericField {
         for child in &self.children {
             child.validate()?;
         }
+        if matches!(self.strategy, Some(Strategy::UntaggedUnion)) {
+            let mut shapes = Vec::new();
+            for child in &self.children {
+                let Some(shape) = UntaggedShape::from_field(child) else {
+                    fail!(
+                        "invalid child for untagged Union field: {}",
+                        child.data_type
+                    );
+                };
+                if shapes.contains(&shape) {
+                    fail!("duplicate shape {shape} in untagged Union field");
+                }
+                shapes.push(shape);
+            }
+        }
         Ok(())
     }
 
     pub(crate) fn validate_dictionary(&self) -> Result<()> {
-        if self.strategy.is_some() {
+        if !matches!(self.strategy, None | Some(Strategy::InconsistentTypes)) {
             fail!(
                 "invalid strategy for Dictionary field: {}",
                 self.strategy.as_ref().unwrap()
@@ -479,6 +734,158 @@ impl GenericField {
     }
 }
 
+/// The shape of a value as seen by untagged unions
+///
+/// Each child of an untagged union accepts values of exactly one shape.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UntaggedShape {
+    Bool,
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+    Str,
+    Sequence,
+    Tuple,
+    Struct,
+    Map,
+    Variant,
+}
+
+impl UntaggedShape {
+    /// Determine the shape of the value started by `event`
+    ///
+    /// For events that do not start a value (e.g., end events or markers),
+    /// `None` is returned.
+    ///
+    pub fn from_event(event: &Event<'_>) -> Option<Self> {
+        match event {
+            Event::Bool(_) => Some(Self::Bool),
+            Event::I8(_) => Some(Self::I8),
+            Event::I16(_) => Some(Self::I16),
+            Event::I32(_) => Some(Self::I32),
+            Event::I64(_) => Some(Self::I64),
+            Event::U8(_) => Some(Self::U8),
+            Event::U16(_) => Some(Self::U16),
+            Event::U32(_) => Some(Self::U32),
+            Event::U64(_) => Some(Self::U64),
+            Event::F32(_) => Some(Self::F32),
+            Event::F64(_) => Some(Self::F64),
+            Event::Str(_) | Event::OwnedStr(_) => Some(Self::Str),
+            Event::StartSequence => Some(Self::Sequence),
+            Event::StartTuple => Some(Self::Tuple),
+            Event::StartStruct => Some(Self::Struct),
+            Event::StartMap => Some(Self::Map),
+            Event::Variant(_, _) | Event::OwnedVariant(_, _) => Some(Self::Variant),
+            _ => None,
+        }
+    }
+
+    /// Determine the shape of the values accepted by `field`
+    ///
+    /// For fields that do not accept any values (i.e., null fields), `None` is
+    /// returned.
+    ///
+    pub fn from_field(field: &GenericField) -> Option<Self> {
+        use GenericDataType as D;
+        match (&field.data_type, &field.strategy) {
+            (D::Bool, _) => Some(Self::Bool),
+            (D::I8, _) => Some(Self::I8),
+            (D::I16, _) => Some(Self::I16),
+            (D::I32, _) | (D::Date32, None) => Some(Self::I32),
+            (D::I64, _) => Some(Self::I64),
+            (D::U8, _) => Some(Self::U8),
+            (D::U16, _) => Some(Self::U16),
+            (D::U32, _) => Some(Self::U32),
+            (D::U64, _) => Some(Self::U64),
+            (D::F16 | D::F32, _) => Some(Self::F32),
+            (D::F64, _) => Some(Self::F64),
+            (D::Utf8 | D::LargeUtf8 | D::Dictionary | D::Date32 | D::Date64, _) => Some(Self::Str),
+            (D::List | D::LargeList, Some(Strategy::TupleAsList)) => Some(Self::Tuple),
+            (D::List | D::LargeList, _) => Some(Self::Sequence),
+            (D::Struct, Some(Strategy::TupleAsStruct)) => Some(Self::Tuple),
+            (D::Struct, Some(Strategy::MapAsStruct)) | (D::Map, _) => Some(Self::Map),
+            (D::Struct, _) => Some(Self::Struct),
+            (D::Union, _) => Some(Self::Variant),
+            (D::Null, _) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for UntaggedShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The Arrow type of traced strings
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringType {
+    /// Strings with 32 bit offsets, i.e., at most 2 GB of string data per array
+    Utf8,
+    /// Strings with 64 bit offsets (the default)
+    LargeUtf8,
+}
+
+impl StringType {
+    fn data_type(self) -> GenericDataType {
+        match self {
+            Self::Utf8 => GenericDataType::Utf8,
+            Self::LargeUtf8 => GenericDataType::LargeUtf8,
+        }
+    }
+}
+
+/// The Arrow type of traced lists
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListType {
+    /// Lists with 32 bit offsets, i.e., at most `i32::MAX` elements per array
+    List,
+    /// Lists with 64 bit offsets (the default)
+    LargeList,
+}
+
+impl ListType {
+    fn data_type(self) -> GenericDataType {
+        match self {
+            Self::List => GenericDataType::List,
+            Self::LargeList => GenericDataType::LargeList,
+        }
+    }
+}
+
+/// The type of the keys of dictionary encoded strings
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DictionaryKeyType {
+    U8,
+    U16,
+    /// The default, as `polars` cannot handle 64 bit keys in its default
+    /// configuration
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    /// The smallest unsigned type that can represent the distinct values
+    /// encountered during tracing (`U8`, `U16`, or `U32`)
+    ///
+    /// Serialization fails, if the serialized data contains more distinct
+    /// values than can be represented by the chosen type.
+    ///
+    Auto,
+}
+
 /// Configure how the schema is traced
 ///
 /// Example:
@@ -507,6 +914,118 @@ pub struct TracingOptions {
     /// `Dictionary(UInt64, LargeUtf8)`.
     ///
     pub string_dictionary_encoding: bool,
+
+    /// If `true`, strings are dictionary encoded depending on the number of
+    /// distinct values. The default is `false`.
+    ///
+    /// A string field is dictionary encoded, if it contains at most
+    /// [`dictionary_max_distinct`][TracingOptions::dictionary_max_distinct]
+    /// distinct values and the ratio of distinct values to all values is at
+    /// most [`dictionary_max_ratio`][TracingOptions::dictionary_max_ratio]. If
+    /// [`string_dictionary_encoding`][TracingOptions::string_dictionary_encoding]
+    /// is `true`, all strings are dictionary encoded regardless of this option.
+    ///
+    pub auto_dictionary_encoding: bool,
+
+    /// The maximum number of distinct values of dictionary encoded strings
+    /// with `auto_dictionary_encoding`. The default is `1000`.
+    ///
+    /// At most this many distinct values are kept in memory per field during
+    /// tracing.
+    ///
+    pub dictionary_max_distinct: usize,
+
+    /// The maximum ratio of distinct values to all values of dictionary encoded
+    /// strings with `auto_dictionary_encoding`. The default is `0.5`.
+    ///
+    pub dictionary_max_ratio: f64,
+
+    /// The type of the keys of dictionary encoded strings. The default is
+    /// [`DictionaryKeyType::U32`].
+    ///
+    pub dictionary_key_type: DictionaryKeyType,
+
+    /// If `true` serialize tuples with elements of a single type as lists. The
+    /// default is `false`. See [`Strategy::TupleAsList`] for details.
+    ///
+    /// Tuples with elements of different types are always serialized as
+    /// structs.
+    ///
+    pub tuple_as_list: bool,
+
+    /// If `true`, fields with values of differing shapes are traced as unions
+    /// with one child per shape. The default is `false`. See
+    /// [`Strategy::UntaggedUnion`] for details.
+    ///
+    /// This option is required to trace `#[serde(untagged)]` enums whose
+    /// variants differ in shape.
+    ///
+    pub untagged_unions: bool,
+
+    /// If `true`, numbers of differing types are coerced into a common type.
+    /// The default is `false`.
+    ///
+    /// Integers are widened to the smallest integer type that can hold both
+    /// (e.g., `u8` and `i16` give `i16`, `u32` and `i32` give `i64`), mixed
+    /// integers and floats give `f64`. `u64` mixed with signed integers gives
+    /// `i64`, if all `u64` values fit, and `f64` otherwise, losing precision
+    /// beyond 2^53. If `false`, tracing fails for numbers of differing types.
+    /// Coerced fields are marked with [`Strategy::InconsistentTypes`] to
+    /// convert the values in serialization.
+    ///
+    pub coerce_numbers: bool,
+
+    /// If `true`, primitive values mixed with strings are coerced into strings.
+    /// The default is `false`.
+    ///
+    /// Coerced fields are marked with [`Strategy::InconsistentTypes`] and the
+    /// values are formatted as strings in serialization. Together with
+    /// [`coerce_numbers`][TracingOptions::coerce_numbers] this option allows
+    /// to trace data with inconsistent types, e.g., from JSON sources. As an
+    /// alternative, [`untagged_unions`][TracingOptions::untagged_unions]
+    /// traces values of differing types as a union of the observed types.
+    ///
+    pub coerce_to_string: bool,
+
+    /// If `true`, the content of strings is inspected to detect dates. The
+    /// default is `false`.
+    ///
+    /// Fields, whose values are all RFC 3339 datetimes with timezone (e.g.,
+    /// `"2023-01-31T12:00:00Z"`), are traced as `Date64` with
+    /// [`Strategy::UtcStrAsDate64`]. Fields, whose values are all datetimes
+    /// without timezone (e.g., `"2023-01-31T12:00:00"`), are traced as `Date64`
+    /// with [`Strategy::NaiveStrAsDate64`]. Fields, whose values are all dates
+    /// (e.g., `"2023-01-31"`), are traced as `Date32` with
+    /// [`Strategy::NaiveStrAsDate32`]. Fields with mixed formats are traced as
+    /// strings.
+    ///
+    pub guess_dates: bool,
+
+    /// The type of traced strings. The default is [`StringType::LargeUtf8`].
+    ///
+    /// Some consumers of Arrow data only support strings with 32 bit offsets.
+    /// With [`StringType::Utf8`], serialization fails if the string data of an
+    /// array exceeds the range of 32 bit offsets.
+    ///
+    pub string_type: StringType,
+
+    /// The type of traced lists. The default is [`ListType::LargeList`].
+    ///
+    /// Some consumers of Arrow data only support lists with 32 bit offsets.
+    /// With [`ListType::List`], serialization fails if the total number of
+    /// list elements of an array exceeds the range of 32 bit offsets.
+    ///
+    pub list_type: ListType,
+
+    /// Fields that replace the traced fields at the given paths. The default
+    /// is empty.
+    ///
+    /// The keys are the paths prefixed with `$`, e.g., `"$.b.dt"` for the
+    /// field `dt` of the struct field `b` or `"$"` for the root. Prefer
+    /// [`overwrite`][TracingOptions::overwrite], which builds the keys and
+    /// validates the fields.
+    ///
+    pub overwrites: BTreeMap<String, GenericField>,
 }
 
 impl Default for TracingOptions {
@@ -515,6 +1034,18 @@ impl Default for TracingOptions {
             allow_null_fields: false,
             map_as_struct: true,
             string_dictionary_encoding: false,
+            auto_dictionary_encoding: false,
+            dictionary_max_distinct: 1000,
+            dictionary_max_ratio: 0.5,
+            dictionary_key_type: DictionaryKeyType::U32,
+            tuple_as_list: false,
+            untagged_unions: false,
+            coerce_numbers: false,
+            coerce_to_string: false,
+            guess_dates: false,
+            string_type: StringType::LargeUtf8,
+            list_type: ListType::LargeList,
+            overwrites: BTreeMap::new(),
         }
     }
 }
@@ -541,6 +1072,134 @@ impl TracingOptions {
         self.string_dictionary_encoding = value;
         self
     }
+
+    /// Set the `auto_dictionary_encoding` value
+    pub fn auto_dictionary_encoding(mut self, value: bool) -> Self {
+        self.auto_dictionary_encoding = value;
+        self
+    }
+
+    /// Set the `dictionary_max_distinct` value
+    pub fn dictionary_max_distinct(mut self, value: usize) -> Self {
+        self.dictionary_max_distinct = value;
+        self
+    }
+
+    /// Set the `dictionary_max_ratio` value
+    pub fn dictionary_max_ratio(mut self, value: f64) -> Self {
+        self.dictionary_max_ratio = value;
+        self
+    }
+
+    /// Set the `dictionary_key_type` value
+    pub fn dictionary_key_type(mut self, value: DictionaryKeyType) -> Self {
+        self.dictionary_key_type = value;
+        self
+    }
+
+    /// Set the `tuple_as_list` value
+    pub fn tuple_as_list(mut self, value: bool) -> Self {
+        self.tuple_as_list = value;
+        self
+    }
+
+    /// Set the `untagged_unions` value
+    pub fn untagged_unions(mut self, value: bool) -> Self {
+        self.untagged_unions = value;
+        self
+    }
+
+    /// Set the `coerce_numbers` value
+    pub fn coerce_numbers(mut self, value: bool) -> Self {
+        self.coerce_numbers = value;
+        self
+    }
+
+    /// Set the `coerce_to_string` value
+    pub fn coerce_to_string(mut self, value: bool) -> Self {
+        self.coerce_to_string = value;
+        self
+    }
+
+    /// Set the `guess_dates` value
+    pub fn guess_dates(mut self, value: bool) -> Self {
+        self.guess_dates = value;
+        self
+    }
+
+    /// Set the `string_type` value
+    pub fn string_type(mut self, value: StringType) -> Self {
+        self.string_type = value;
+        self
+    }
+
+    /// Set the `list_type` value
+    pub fn list_type(mut self, value: ListType) -> Self {
+        self.list_type = value;
+        self
+    }
+
+    /// Use the given field instead of tracing the field at `path`
+    ///
+    /// The path is given by the names of the nested fields joined by dots,
+    /// e.g., `"b.dt"` for the field `dt` of the struct field `b`. The name of
+    /// the given field is ignored, its data type, strategy and nullability
+    /// replace the traced values. Any values encountered at this path during
+    /// tracing are not inspected. The field can be given as a reference to an
+    /// `arrow` or `arrow2` field.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # #[cfg(feature = "arrow2-0-17")]
+    /// # fn main() {
+    /// # use serde_arrow::_impl::arrow2 as arrow2;
+    /// use arrow2::datatypes::{DataType, Field};
+    /// use serde::Serialize;
+    /// use serde_arrow::{
+    ///     arrow2::serialize_into_fields,
+    ///     schema::{Strategy, TracingOptions},
+    /// };
+    ///
+    /// ##[derive(Serialize)]
+    /// struct Outer {
+    ///     b: Nested,
+    /// }
+    ///
+    /// ##[derive(Serialize)]
+    /// struct Nested {
+    ///     dt: String,
+    /// }
+    ///
+    /// let dt_field = Field::new("dt", DataType::Date64, false)
+    ///     .with_metadata(Strategy::NaiveStrAsDate64.into());
+    /// let options = TracingOptions::default().overwrite("b.dt", &dt_field).unwrap();
+    ///
+    /// let items = [Outer { b: Nested { dt: String::from("2023-01-31T12:00:00") } }];
+    /// let fields = serialize_into_fields(&items, options).unwrap();
+    ///
+    /// let expected = vec![Field::new("b", DataType::Struct(vec![dt_field]), false)];
+    /// assert_eq!(fields, expected);
+    /// # }
+    /// # #[cfg(not(feature = "arrow2-0-17"))]
+    /// # fn main() {}
+    /// ```
+    ///
+    pub fn overwrite<P: AsRef<str>, F: TryInto<GenericField, Error = Error>>(
+        mut self,
+        path: P,
+        field: F,
+    ) -> Result<Self> {
+        let field = field.try_into()?;
+        field.validate()?;
+
+        let path = match path.as_ref() {
+            "" => String::from("$"),
+            path => format!("$.{path}"),
+        };
+        self.overwrites.insert(path, field);
+        Ok(self)
+    }
 }
 
 pub enum Tracer {
@@ -551,10 +1210,15 @@ pub enum Tracer {
     Tuple(TupleTracer),
     Union(UnionTracer),
     Map(MapTracer),
+    Untagged(UntaggedTracer),
+    Overwrite(OverwriteTracer),
 }
 
 impl Tracer {
-    pub fn new(path: String, options: TracingOptions) -> Self {
+    pub fn new(path: String, options: Arc<TracingOptions>) -> Self {
+        if let Some(field) = options.overwrites.get(&path) {
+            return Self::Overwrite(OverwriteTracer::new(field.clone()));
+        }
         Self::Unknown(UnknownTracer::new(path, options))
     }
 
@@ -568,6 +1232,8 @@ impl Tracer {
             Tuple(t) => t.to_field(name),
             Union(t) => t.to_field(name),
             Struct(t) => t.to_field(name),
+            Untagged(t) => t.to_field(name),
+            Overwrite(t) => t.to_field(name),
         }
     }
 
@@ -593,6 +1259,11 @@ impl Tracer {
             Struct(t) => {
                 t.nullable = true;
             }
+            Untagged(t) => {
+                t.nullable = true;
+            }
+            // NOTE: the nullability of overwritten fields is not modified
+            Overwrite(_) => {}
         }
     }
 }
@@ -604,93 +1275,23 @@ impl EventSink for Tracer {
         match self {
             // NOTE: unknown tracer is the only tracer that change the internal type
             Self::Unknown(tracer) => match event {
-                Event::Some | Event::Null => tracer.nullable = true,
-                Event::Bool(_)
-                | Event::I8(_)
-                | Event::I16(_)
-                | Event::I32(_)
-                | Event::I64(_)
-                | Event::U8(_)
-                | Event::U16(_)
-                | Event::U32(_)
-                | Event::U64(_)
-                | Event::F32(_)
-                | Event::F64(_)
-                | Event::Str(_)
-                | Event::OwnedStr(_) => {
-                    let mut tracer = PrimitiveTracer::new(
-                        tracer.nullable,
-                        tracer.options.string_dictionary_encoding,
-                        tracer.options.allow_null_fields,
-                    );
-                    tracer.accept(event)?;
-                    *self = Tracer::Primitive(tracer)
-                }
-                Event::StartSequence => {
-                    let mut tracer = ListTracer::new(
-                        tracer.path.clone(),
-                        tracer.options.clone(),
-                        tracer.nullable,
-                    );
-                    tracer.accept(event)?;
-                    *self = Tracer::List(tracer);
-                }
-                Event::StartStruct => {
-                    let mut tracer = StructTracer::new(
-                        tracer.path.clone(),
-                        tracer.options.clone(),
-                        StructMode::Struct,
-                        tracer.nullable,
-                    );
-                    tracer.accept(event)?;
-                    *self = Tracer::Struct(tracer);
-                }
-                Event::StartTuple => {
-                    let mut tracer = TupleTracer::new(
+                Event::Some | Event::Null => tracer.nullable = true,
+                ev if tracer.options.untagged_unions
+                    && UntaggedShape::from_event(&ev).is_some() =>
+                {
+                    let mut tracer = UntaggedTracer::new(
                         tracer.path.clone(),
                         tracer.options.clone(),
                         tracer.nullable,
                     );
-                    tracer.accept(event)?;
-                    *self = Tracer::Tuple(tracer);
-                }
-                Event::StartMap => {
-                    if tracer.options.map_as_struct {
-                        let mut tracer = StructTracer::new(
-                            tracer.path.clone(),
-                            tracer.options.clone(),
-                            StructMode::Map,
-                            tracer.nullable,
-                        );
-                        tracer.accept(event)?;
-                        *self = Tracer::Struct(tracer);
-                    } else {
-                        let mut tracer = MapTracer::new(
-                            tracer.path.clone(),
-                            tracer.options.clone(),
-                            tracer.nullable,
-                        );
-                        tracer.accept(event)?;
-                        *self = Tracer::Map(tracer);
-                    }
+                    tracer.accept(ev)?;
+                    *self = Tracer::Untagged(tracer);
                 }
-                Event::Variant(_, _) => {
-                    let mut tracer = UnionTracer::new(
-                        tracer.path.clone(),
-                        tracer.options.clone(),
-                        tracer.nullable,
-                    );
-                    tracer.accept(event)?;
-                    *self = Tracer::Union(tracer)
+                ev => {
+                    let mut next = tracer.start(&ev)?;
+                    next.accept(ev)?;
+                    *self = next;
                 }
-                ev if ev.is_end() => fail!(
-                    "Invalid end nesting events for unknown tracer ({path})",
-                    path = tracer.path
-                ),
-                ev => fail!(
-                    "Internal error unmatched event {ev} in Tracer ({path})",
-                    path = tracer.path
-                ),
             },
             Self::List(tracer) => tracer.accept(event)?,
             Self::Struct(tracer) => tracer.accept(event)?,
@@ -698,6 +1299,8 @@ impl EventSink for Tracer {
             Self::Tuple(tracer) => tracer.accept(event)?,
             Self::Union(tracer) => tracer.accept(event)?,
             Self::Map(tracer) => tracer.accept(event)?,
+            Self::Untagged(tracer) => tracer.accept(event)?,
+            Self::Overwrite(_) => {}
         }
         Ok(())
     }
@@ -711,7 +1314,40 @@ impl EventSink for Tracer {
             Self::Tuple(tracer) => tracer.finish(),
             Self::Union(tracer) => tracer.finish(),
             Self::Map(tracer) => tracer.finish(),
+            Self::Untagged(tracer) => tracer.finish(),
+            Self::Overwrite(tracer) => tracer.finish(),
+        }
+    }
+}
+
+/// A tracer that ignores all events and returns a fixed field
+///
+pub struct OverwriteTracer {
+    pub field: GenericField,
+    pub finished: bool,
+}
+
+impl OverwriteTracer {
+    pub fn new(field: GenericField) -> Self {
+        Self {
+            field,
+            finished: false,
+        }
+    }
+
+    pub fn to_field(&self, name: &str) -> Result<GenericField> {
+        if !self.finished {
+            fail!("Cannot build field {name} from unfinished tracer");
         }
+
+        let mut field = self.field.clone();
+        field.name = name.to_owned();
+        Ok(field)
+    }
+
+    pub fn finish(&mut self) -> Result<()> {
+        self.finished = true;
+        Ok(())
     }
 }
 
@@ -719,11 +1355,11 @@ pub struct UnknownTracer {
     pub nullable: bool,
     pub finished: bool,
     pub path: String,
-    pub options: TracingOptions,
+    pub options: Arc<TracingOptions>,
 }
 
 impl UnknownTracer {
-    pub fn new(path: String, options: TracingOptions) -> Self {
+    pub fn new(path: String, options: Arc<TracingOptions>) -> Self {
         Self {
             nullable: false,
             finished: false,
@@ -750,6 +1386,77 @@ impl UnknownTracer {
         ))
     }
 
+    /// Build the tracer for the value started by `event`
+    ///
+    /// The event itself is not processed and has to be passed to the returned
+    /// tracer.
+    ///
+    pub fn start(&self, event: &Event<'_>) -> Result<Tracer> {
+        let tracer = match event {
+            Event::Bool(_)
+            | Event::I8(_)
+            | Event::I16(_)
+            | Event::I32(_)
+            | Event::I64(_)
+            | Event::U8(_)
+            | Event::U16(_)
+            | Event::U32(_)
+            | Event::U64(_)
+            | Event::F32(_)
+            | Event::F64(_)
+            | Event::Str(_)
+            | Event::OwnedStr(_) => {
+                Tracer::Primitive(PrimitiveTracer::new(self.nullable, &self.options))
+            }
+            Event::StartSequence => Tracer::List(ListTracer::new(
+                self.path.clone(),
+                self.options.clone(),
+                self.nullable,
+            )),
+            Event::StartStruct => Tracer::Struct(StructTracer::new(
+                self.path.clone(),
+                self.options.clone(),
+                StructMode::Struct,
+                self.nullable,
+            )),
+            Event::StartTuple => Tracer::Tuple(TupleTracer::new(
+                self.path.clone(),
+                self.options.clone(),
+                self.nullable,
+            )),
+            Event::StartMap => {
+                if self.options.map_as_struct {
+                    Tracer::Struct(StructTracer::new(
+                        self.path.clone(),
+                        self.options.clone(),
+                        StructMode::Map,
+                        self.nullable,
+                    ))
+                } else {
+                    Tracer::Map(MapTracer::new(
+                        self.path.clone(),
+                        self.options.clone(),
+                        self.nullable,
+                    ))
+                }
+            }
+            Event::Variant(_, _) => Tracer::Union(UnionTracer::new(
+                self.path.clone(),
+                self.options.clone(),
+                self.nullable,
+            )),
+            ev if ev.is_end() => fail!(
+                "Invalid end nesting events for unknown tracer ({path})",
+                path = self.path
+            ),
+            ev => fail!(
+                "Internal error unmatched event {ev} in Tracer ({path})",
+                path = self.path
+            ),
+        };
+        Ok(tracer)
+    }
+
     pub fn finish(&mut self) -> Result<()> {
         self.finished = true;
         Ok(())
@@ -762,6 +1469,10 @@ pub enum StructMode {
     Map,
 }
 
+// NOTE: internally and adjacently tagged enums are serialized by serde as
+// structs, the tag being an ordinary string field. They cannot be told apart
+// from structs and are traced as flat structs with the fields of all variants.
+// Fields of different variants with the same name share a single field.
 pub struct StructTracer {
     pub mode: StructMode,
     pub field_tracers: Vec<Tracer>,
@@ -774,18 +1485,24 @@ pub struct StructTracer {
     pub seen_previous_items: BTreeSet<usize>,
     pub finished: bool,
     pub path: String,
-    pub options: TracingOptions,
+    pub options: Arc<TracingOptions>,
 }
 
 #[derive(Debug, Clone, Copy)]
 pub enum StructTracerState {
     Start,
     Key,
+    UnitVariantKey(usize),
     Value(usize, usize),
 }
 
 impl StructTracer {
-    pub fn new(path: String, options: TracingOptions, mode: StructMode, nullable: bool) -> Self {
+    pub fn new(
+        path: String,
+        options: Arc<TracingOptions>,
+        mode: StructMode,
+        nullable: bool,
+    ) -> Self {
         Self {
             path,
             options,
@@ -821,6 +1538,24 @@ impl StructTracer {
     pub fn mark_seen(&mut self, field: usize) {
         self.seen_this_item.insert(field);
     }
+
+    /// Get the index of the field for the given key, adding it if required
+    pub fn field_for_key(&mut self, key: &str) -> usize {
+        let field = if let Some(&field) = self.index.get(key) {
+            field
+        } else {
+            let field = self.field_tracers.len();
+            self.field_tracers.push(Tracer::new(
+                format!("{path}.{key}", path = self.path),
+                self.options.clone(),
+            ));
+            self.field_names.push(key.to_owned());
+            self.index.insert(key.to_owned(), field);
+            field
+        };
+        self.mark_seen(field);
+        field
+    }
 }
 
 impl EventSink for StructTracer {
@@ -838,22 +1573,7 @@ impl EventSink for StructTracer {
             }
             (Start, ev) => fail!("Invalid event {ev} for struct tracer in state Start"),
             (Key, E::Item) => Key,
-            (Key, E::Str(key)) => {
-                if let Some(&field) = self.index.get(key) {
-                    self.mark_seen(field);
-                    Value(field, 0)
-                } else {
-                    let field = self.field_tracers.len();
-                    self.field_tracers.push(Tracer::new(
-                        format!("{path}.{key}", path = self.path),
-                        self.options.clone(),
-                    ));
-                    self.field_names.push(key.to_owned());
-                    self.index.insert(key.to_owned(), field);
-                    self.mark_seen(field);
-                    Value(field, 0)
-                }
-            }
+            (Key, E::Str(key)) => Value(self.field_for_key(key), 0),
             (Key, E::EndStruct | E::EndMap) => {
                 if self.item_index == 0 {
                     self.seen_previous_items = self.seen_this_item.clone();
@@ -874,7 +1594,22 @@ impl EventSink for StructTracer {
 
                 Start
             }
-            (Key, ev) => fail!("Invalid event {ev} for struct tracer in state Key"),
+            // non-string keys of maps are formatted as strings, unit variants
+            // are followed by a Null event that is skipped
+            (Key, E::Variant(name, _)) if self.mode == StructMode::Map => {
+                UnitVariantKey(self.field_for_key(name))
+            }
+            (Key, E::OwnedVariant(name, _)) if self.mode == StructMode::Map => {
+                UnitVariantKey(self.field_for_key(&name))
+            }
+            (Key, ev) => match ev.to_key() {
+                Some(key) if self.mode == StructMode::Map => Value(self.field_for_key(&key), 0),
+                _ => fail!("Invalid event {ev} for struct tracer in state Key"),
+            },
+            (UnitVariantKey(field), E::Null) => Value(field, 0),
+            (UnitVariantKey(_), ev) => fail!(
+                "Invalid event {ev} for struct tracer in state UnitVariantKey, only unit variants are supported as keys"
+            ),
             (Value(field, depth), ev) if ev.is_start() => {
                 self.field_tracers[field].accept(ev)?;
                 Value(field, depth + 1)
@@ -925,11 +1660,11 @@ pub struct TupleTracer {
     pub next: TupleTracerState,
     pub finished: bool,
     pub path: String,
-    pub options: TracingOptions,
+    pub options: Arc<TracingOptions>,
 }
 
 impl TupleTracer {
-    pub fn new(path: String, options: TracingOptions, nullable: bool) -> Self {
+    pub fn new(path: String, options: Arc<TracingOptions>, nullable: bool) -> Self {
         Self {
             path,
             options,
@@ -945,6 +1680,17 @@ impl TupleTracer {
             fail!("Cannot build field {name} from unfinished tracer");
         }
 
+        if self.options.tuple_as_list {
+            if let Some(element) = self.to_element_field()? {
+                let data_type = self.options.list_type.data_type();
+                let mut field = GenericField::new(name, data_type, self.nullable);
+                field.children.push(element);
+                field.strategy = Some(Strategy::TupleAsList);
+
+                return Ok(field);
+            }
+        }
+
         let mut field = GenericField::new(name, GenericDataType::Struct, self.nullable);
         for (idx, tracer) in self.field_tracers.iter().enumerate() {
             field.children.push(tracer.to_field(&idx.to_string())?);
@@ -954,6 +1700,20 @@ impl TupleTracer {
         Ok(field)
     }
 
+    /// Get the shared element field, if all elements have the same type
+    fn to_element_field(&self) -> Result<Option<GenericField>> {
+        let mut element: Option<GenericField> = None;
+        for tracer in &self.field_tracers {
+            let field = tracer.to_field("element")?;
+            match &element {
+                Some(element) if *element != field => return Ok(None),
+                Some(_) => {}
+                None => element = Some(field),
+            }
+        }
+        Ok(element)
+    }
+
     fn field_tracer(&mut self, idx: usize) -> &mut Tracer {
         while self.field_tracers.len() <= idx {
             self.field_tracers.push(Tracer::new(
@@ -1045,6 +1805,7 @@ pub struct ListTracer {
     pub next: ListTracerState,
     pub finished: bool,
     pub path: String,
+    pub options: Arc<TracingOptions>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -1055,10 +1816,11 @@ pub enum ListTracerState {
 }
 
 impl ListTracer {
-    pub fn new(path: String, options: TracingOptions, nullable: bool) -> Self {
+    pub fn new(path: String, options: Arc<TracingOptions>, nullable: bool) -> Self {
         Self {
             path: path.clone(),
-            item_tracer: Box::new(Tracer::new(path, options)),
+            item_tracer: Box::new(Tracer::new(path, options.clone())),
+            options,
             nullable,
             next: ListTracerState::WaitForStart,
             finished: false,
@@ -1070,7 +1832,8 @@ impl ListTracer {
             fail!("Cannot build field {name} from unfinished tracer");
         }
 
-        let mut field = GenericField::new(name, GenericDataType::LargeList, self.nullable);
+        let data_type = self.options.list_type.data_type();
+        let mut field = GenericField::new(name, data_type, self.nullable);
         field.children.push(self.item_tracer.to_field("element")?);
 
         Ok(field)
@@ -1142,11 +1905,11 @@ pub struct UnionTracer {
     pub next: UnionTracerState,
     pub finished: bool,
     pub path: String,
-    pub options: TracingOptions,
+    pub options: Arc<TracingOptions>,
 }
 
 impl UnionTracer {
-    pub fn new(path: String, options: TracingOptions, nullable: bool) -> Self {
+    pub fn new(path: String, options: Arc<TracingOptions>, nullable: bool) -> Self {
         Self {
             path,
             options,
@@ -1281,6 +2044,145 @@ pub enum UnionTracerState {
     Active(usize, usize),
 }
 
+pub struct UntaggedTracer {
+    pub shapes: Vec<UntaggedShape>,
+    pub tracers: Vec<Tracer>,
+    pub nullable: bool,
+    pub next: UnionTracerState,
+    pub finished: bool,
+    pub path: String,
+    pub options: Arc<TracingOptions>,
+}
+
+impl UntaggedTracer {
+    pub fn new(path: String, options: Arc<TracingOptions>, nullable: bool) -> Self {
+        Self {
+            path,
+            options,
+            shapes: Vec::new(),
+            tracers: Vec::new(),
+            nullable,
+            next: UnionTracerState::Inactive,
+            finished: false,
+        }
+    }
+
+    pub fn to_field(&self, name: &str) -> Result<GenericField> {
+        if !self.finished {
+            fail!("Cannot build field {name} from unfinished tracer");
+        }
+
+        // NOTE: values of a single shape do not require a union
+        if let [tracer] = self.tracers.as_slice() {
+            let mut field = tracer.to_field(name)?;
+            field.nullable |= self.nullable;
+            return Ok(field);
+        }
+
+        if self.nullable {
+            fail!(
+                "Nullable unions are not supported ({path})",
+                path = self.path
+            );
+        }
+
+        let mut field = GenericField::new(name, GenericDataType::Union, false)
+            .with_strategy(Strategy::UntaggedUnion);
+        for (idx, tracer) in self.tracers.iter().enumerate() {
+            field.children.push(tracer.to_field(&idx.to_string())?);
+        }
+
+        Ok(field)
+    }
+
+    fn ensure_shape(&mut self, event: &Event<'_>) -> Result<usize> {
+        let Some(shape) = UntaggedShape::from_event(event) else {
+            fail!(
+                "Invalid event {event} for UntaggedTracer in State Inactive ({path})",
+                path = self.path
+            );
+        };
+
+        if let Some(idx) = self.shapes.iter().position(|s| *s == shape) {
+            return Ok(idx);
+        }
+
+        let idx = self.shapes.len();
+        let unknown = UnknownTracer::new(
+            format!("{path}.{idx}", path = self.path),
+            self.options.clone(),
+        );
+        self.tracers.push(unknown.start(event)?);
+        self.shapes.push(shape);
+
+        Ok(idx)
+    }
+}
+
+impl EventSink for UntaggedTracer {
+    macros::forward_specialized_to_generic!();
+
+    fn accept(&mut self, event: Event<'_>) -> Result<()> {
+        type S = UnionTracerState;
+        type E<'a> = Event<'a>;
+
+        self.next = match self.next {
+            S::Inactive => match event {
+                E::Some | E::Null => {
+                    self.nullable = true;
+                    S::Inactive
+                }
+                ev => {
+                    let idx = self.ensure_shape(&ev)?;
+                    let next = if ev.is_start() {
+                        S::Active(idx, 1)
+                    } else if matches!(ev, E::Variant(_, _) | E::OwnedVariant(_, _)) {
+                        S::Active(idx, 0)
+                    } else {
+                        S::Inactive
+                    };
+                    self.tracers[idx].accept(ev)?;
+                    next
+                }
+            },
+            S::Active(idx, depth) => match event {
+                ev if ev.is_start() => {
+                    self.tracers[idx].accept(ev)?;
+                    S::Active(idx, depth + 1)
+                }
+                ev if ev.is_end() => {
+                    self.tracers[idx].accept(ev)?;
+                    match depth {
+                        0 => fail!("Invalid end event at depth 0 in UntaggedTracer"),
+                        1 => S::Inactive,
+                        _ => S::Active(idx, depth - 1),
+                    }
+                }
+                ev @ (E::Item | E::Some | E::Variant(_, _) | E::OwnedVariant(_, _)) => {
+                    self.tracers[idx].accept(ev)?;
+                    S::Active(idx, depth)
+                }
+                ev => {
+                    self.tracers[idx].accept(ev)?;
+                    match depth {
+                        0 => S::Inactive,
+                        _ => S::Active(idx, depth),
+                    }
+                }
+            },
+        };
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        for tracer in &mut self.tracers {
+            tracer.finish()?;
+        }
+        self.finished = true;
+        Ok(())
+    }
+}
+
 pub struct MapTracer {
     pub path: String,
     pub key: Box<Tracer>,
@@ -1291,7 +2193,7 @@ pub struct MapTracer {
 }
 
 impl MapTracer {
-    pub fn new(path: String, options: TracingOptions, nullable: bool) -> Self {
+    pub fn new(path: String, options: Arc<TracingOptions>, nullable: bool) -> Self {
         Self {
             nullable,
             key: Box::new(Tracer::new(format!("{path}.$key"), options.clone())),
@@ -1420,20 +2322,50 @@ pub enum MapTracerState {
 }
 
 pub struct PrimitiveTracer {
+    pub string_type: StringType,
     pub string_dictionary_encoding: bool,
+    pub auto_dictionary_encoding: bool,
+    pub dictionary_max_distinct: usize,
+    pub dictionary_max_ratio: f64,
+    pub dictionary_key_type: DictionaryKeyType,
+    pub distinct_values: HashSet<String>,
+    pub distinct_values_overflow: bool,
+    pub num_values: usize,
     pub allow_null_fields: bool,
+    pub coerce_numbers: bool,
+    pub coerce_to_string: bool,
+    /// Whether values of differing types were coerced into a common type
+    pub coerced: bool,
+    /// Whether any `u64` value exceeded the range of `i64`
+    pub large_u64_values: bool,
+    pub guess_dates: bool,
+    pub string_format: StringFormat,
     pub item_type: GenericDataType,
     pub nullable: bool,
     pub finished: bool,
 }
 
 impl PrimitiveTracer {
-    pub fn new(nullable: bool, string_dictionary_encoding: bool, allow_null_fields: bool) -> Self {
+    pub fn new(nullable: bool, options: &TracingOptions) -> Self {
         Self {
             item_type: GenericDataType::Null,
-            allow_null_fields,
+            allow_null_fields: options.allow_null_fields,
+            coerce_numbers: options.coerce_numbers,
+            coerce_to_string: options.coerce_to_string,
+            coerced: false,
+            large_u64_values: false,
+            guess_dates: options.guess_dates,
+            string_format: StringFormat::Unknown,
             nullable,
-            string_dictionary_encoding,
+            string_type: options.string_type,
+            string_dictionary_encoding: options.string_dictionary_encoding,
+            auto_dictionary_encoding: options.auto_dictionary_encoding,
+            dictionary_max_distinct: options.dictionary_max_distinct,
+            dictionary_max_ratio: options.dictionary_max_ratio,
+            dictionary_key_type: options.dictionary_key_type,
+            distinct_values: HashSet::new(),
+            distinct_values_overflow: false,
+            num_values: 0,
             finished: false,
         }
     }
@@ -1453,18 +2385,248 @@ impl PrimitiveTracer {
         }
 
         match self.item_type {
-            dt @ (D::LargeUtf8 | D::Utf8) => {
-                if !self.string_dictionary_encoding {
-                    Ok(GenericField::new(name, dt, self.nullable))
+            D::LargeUtf8 | D::Utf8 if self.guess_dates && self.string_format.is_date() => {
+                let (dt, strategy) = match self.string_format {
+                    StringFormat::UtcDateTime => (D::Date64, Strategy::UtcStrAsDate64),
+                    StringFormat::NaiveDateTime => (D::Date64, Strategy::NaiveStrAsDate64),
+                    _ => (D::Date32, Strategy::NaiveStrAsDate32),
+                };
+                Ok(GenericField::new(name, dt, self.nullable).with_strategy(strategy))
+            }
+            D::LargeUtf8 | D::Utf8 => {
+                let dt = self.string_type.data_type();
+                if !self.string_dictionary_encoding && !self.use_auto_dictionary_encoding() {
+                    Ok(self.mark_coerced(GenericField::new(name, dt, self.nullable)))
                 } else {
                     let field = GenericField::new(name, D::Dictionary, self.nullable)
-                        .with_child(GenericField::new("key", D::U32, false))
+                        .with_child(GenericField::new("key", self.dictionary_key(), false))
                         .with_child(GenericField::new("value", dt, false));
-                    Ok(field)
+                    Ok(self.mark_coerced(field))
+                }
+            }
+            dt => Ok(self.mark_coerced(GenericField::new(name, dt, self.nullable))),
+        }
+    }
+
+    /// Mark fields traced from values of differing types
+    ///
+    /// The strategy instructs the serialization to convert the values into
+    /// the common type.
+    ///
+    fn mark_coerced(&self, field: GenericField) -> GenericField {
+        if self.coerced {
+            field.with_strategy(Strategy::InconsistentTypes)
+        } else {
+            field
+        }
+    }
+
+    /// Check whether the distinct values allow automatic dictionary encoding
+    ///
+    fn use_auto_dictionary_encoding(&self) -> bool {
+        self.auto_dictionary_encoding
+            && !self.distinct_values_overflow
+            && self.distinct_values.len() <= self.dictionary_max_distinct
+            && self.num_values > 0
+            && (self.distinct_values.len() as f64)
+                <= self.dictionary_max_ratio * (self.num_values as f64)
+    }
+
+    /// Determine the data type of the dictionary keys
+    ///
+    fn dictionary_key(&self) -> GenericDataType {
+        type D = GenericDataType;
+        type K = DictionaryKeyType;
+
+        match self.dictionary_key_type {
+            K::U8 => D::U8,
+            K::U16 => D::U16,
+            K::U32 => D::U32,
+            K::U64 => D::U64,
+            K::I8 => D::I8,
+            K::I16 => D::I16,
+            K::I32 => D::I32,
+            K::I64 => D::I64,
+            K::Auto if self.distinct_values_overflow => D::U32,
+            K::Auto if self.distinct_values.len() <= 1 << 8 => D::U8,
+            K::Auto if self.distinct_values.len() <= 1 << 16 => D::U16,
+            K::Auto => D::U32,
+        }
+    }
+
+    /// Check whether the distinct values of strings are required
+    ///
+    /// They are only required for fields that may be dictionary encoded.
+    ///
+    fn track_distinct_values(&self) -> bool {
+        self.auto_dictionary_encoding || self.track_distinct_keys()
+    }
+
+    /// Check whether the distinct values are required to determine the key type
+    ///
+    fn track_distinct_keys(&self) -> bool {
+        self.string_dictionary_encoding && self.dictionary_key_type == DictionaryKeyType::Auto
+    }
+
+    fn record_distinct_value(&mut self, val: &str) {
+        self.num_values += 1;
+        if self.distinct_values_overflow || self.distinct_values.contains(val) {
+            return;
+        }
+
+        let mut limit = 0;
+        if self.auto_dictionary_encoding {
+            limit = limit.max(self.dictionary_max_distinct);
+        }
+        if self.track_distinct_keys() {
+            // NOTE: the number of values required to distinguish between u16 and u32 keys
+            limit = limit.max((1 << 16) + 1);
+        }
+
+        if self.distinct_values.len() >= limit {
+            // NOTE: the values are no longer required, once the limit is exceeded
+            self.distinct_values_overflow = true;
+            self.distinct_values = HashSet::new();
+            return;
+        }
+        self.distinct_values.insert(val.to_owned());
+    }
+
+    /// Determine the common type of two differing primitive types
+    ///
+    /// Returns `None`, if the types cannot be coerced with the configured
+    /// rules.
+    ///
+    fn coerce(&self, left: GenericDataType, right: GenericDataType) -> Option<GenericDataType> {
+        type D = GenericDataType;
+
+        if self.coerce_to_string && (left == D::LargeUtf8 || right == D::LargeUtf8) {
+            return Some(D::LargeUtf8);
+        }
+        if !self.coerce_numbers {
+            return None;
+        }
+        // NOTE: the range of the u64 values is known, allow i64 if they fit
+        let is_signed = |ty| matches!(ty, D::I8 | D::I16 | D::I32 | D::I64);
+        if !self.large_u64_values
+            && ((left == D::U64 && is_signed(right)) || (is_signed(left) && right == D::U64))
+        {
+            return Some(D::I64);
+        }
+        coerce_numeric_types(left, right)
+    }
+}
+
+/// Determine the common type of two differing numeric types
+///
+/// Integers are widened to cover the ranges of both types. Mixed integers and
+/// floats result in `F64`. `U64` mixed with signed integers also results in
+/// `F64`, as no integer type covers both ranges. In both cases, integers
+/// beyond 2^53 lose precision. Returns `None`, if either type is not numeric.
+///
+/// During tracing, the observed values are known and `U64` values that fit
+/// into `I64` are coerced with signed integers into `I64` instead.
+///
+pub fn coerce_numeric_types(
+    left: GenericDataType,
+    right: GenericDataType,
+) -> Option<GenericDataType> {
+    type D = GenericDataType;
+
+    fn integer_info(ty: GenericDataType) -> Option<(bool, u32)> {
+        match ty {
+            D::I8 => Some((true, 8)),
+            D::I16 => Some((true, 16)),
+            D::I32 => Some((true, 32)),
+            D::I64 => Some((true, 64)),
+            D::U8 => Some((false, 8)),
+            D::U16 => Some((false, 16)),
+            D::U32 => Some((false, 32)),
+            D::U64 => Some((false, 64)),
+            _ => None,
+        }
+    }
+
+    fn integer_type(signed: bool, bits: u32) -> GenericDataType {
+        match (signed, bits) {
+            (true, 8) => D::I8,
+            (true, 16) => D::I16,
+            (true, 32) => D::I32,
+            (true, _) => D::I64,
+            (false, 8) => D::U8,
+            (false, 16) => D::U16,
+            (false, 32) => D::U32,
+            (false, _) => D::U64,
+        }
+    }
+
+    let is_float = |ty| matches!(ty, D::F32 | D::F64);
+    match (integer_info(left), integer_info(right)) {
+        (Some((left_signed, left_bits)), Some((right_signed, right_bits))) => {
+            if left_signed == right_signed {
+                Some(integer_type(left_signed, left_bits.max(right_bits)))
+            } else {
+                let (signed_bits, unsigned_bits) = if left_signed {
+                    (left_bits, right_bits)
+                } else {
+                    (right_bits, left_bits)
+                };
+                // NOTE: unsigned integers require an additional bit when stored as signed integers
+                let bits = signed_bits.max(2 * unsigned_bits);
+                if bits > 64 {
+                    Some(D::F64)
+                } else {
+                    Some(integer_type(true, bits))
                 }
             }
-            dt => Ok(GenericField::new(name, dt, self.nullable)),
         }
+        (Some(_), None) if is_float(right) => Some(D::F64),
+        (None, Some(_)) if is_float(left) => Some(D::F64),
+        (None, None) if is_float(left) && is_float(right) => Some(D::F64),
+        _ => None,
+    }
+}
+
+/// The format of the strings encountered by a [PrimitiveTracer]
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringFormat {
+    Unknown,
+    UtcDateTime,
+    NaiveDateTime,
+    NaiveDate,
+    Other,
+}
+
+impl StringFormat {
+    pub fn guess(val: &str) -> Self {
+        use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+        // NOTE: use the same parsers as in serialization
+        if val.parse::<NaiveDate>().is_ok() {
+            Self::NaiveDate
+        } else if val.parse::<DateTime<Utc>>().is_ok() {
+            Self::UtcDateTime
+        } else if val.parse::<NaiveDateTime>().is_ok() {
+            Self::NaiveDateTime
+        } else {
+            Self::Other
+        }
+    }
+
+    pub fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Unknown, other) => other,
+            (this, other) if this == other => this,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn is_date(self) -> bool {
+        matches!(
+            self,
+            Self::UtcDateTime | Self::NaiveDateTime | Self::NaiveDate
+        )
     }
 }
 
@@ -1475,47 +2637,60 @@ impl EventSink for PrimitiveTracer {
         type D = GenericDataType;
         type E<'a> = Event<'a>;
 
-        match (event, self.item_type) {
-            (E::Some | Event::Null, _) => {
+        let ty = match &event {
+            E::Some | E::Null => {
                 self.nullable = true;
+                return Ok(());
             }
-            (E::Bool(_), D::Bool | D::Null) => {
-                self.item_type = D::Bool;
-            }
-            (E::I8(_), D::I8 | D::Null) => {
-                self.item_type = D::I8;
-            }
-            (E::I16(_), D::I16 | D::Null) => {
-                self.item_type = D::I16;
-            }
-            (E::I32(_), D::I32 | D::Null) => {
-                self.item_type = D::I32;
-            }
-            (E::I64(_), D::I64 | D::Null) => {
-                self.item_type = D::I64;
-            }
-            (E::U8(_), D::U8 | D::Null) => {
-                self.item_type = D::U8;
-            }
-            (E::U16(_), D::U16 | D::Null) => {
-                self.item_type = D::U16;
-            }
-            (E::U32(_), D::U32 | D::Null) => {
-                self.item_type = D::U32;
-            }
-            (E::U64(_), D::U64 | D::Null) => {
-                self.item_type = D::U64;
-            }
-            (E::F32(_), D::F32 | D::Null) => {
-                self.item_type = D::F32;
-            }
-            (E::F64(_), D::F64 | D::Null) => {
-                self.item_type = D::F64;
-            }
-            (E::Str(_) | E::OwnedStr(_), D::LargeUtf8 | D::Null) => {
-                self.item_type = D::LargeUtf8;
+            E::Bool(_) => D::Bool,
+            E::I8(_) => D::I8,
+            E::I16(_) => D::I16,
+            E::I32(_) => D::I32,
+            E::I64(_) => D::I64,
+            E::U8(_) => D::U8,
+            E::U16(_) => D::U16,
+            E::U32(_) => D::U32,
+            E::U64(_) => D::U64,
+            E::F32(_) => D::F32,
+            E::F64(_) => D::F64,
+            E::Str(_) | E::OwnedStr(_) => D::LargeUtf8,
+            ev => fail!(
+                "Cannot accept event {ev} for primitive type {ty}",
+                ty = self.item_type
+            ),
+        };
+
+        if let E::U64(val) = &event {
+            self.large_u64_values |= i64::try_from(*val).is_err();
+        }
+
+        self.item_type = if self.item_type == D::Null || self.item_type == ty {
+            ty
+        } else if let Some(coerced) = self.coerce(self.item_type, ty) {
+            self.coerced = true;
+            coerced
+        } else {
+            fail!(
+                "Cannot accept event {event} for primitive type {ty}",
+                ty = self.item_type
+            );
+        };
+
+        if self.track_distinct_values() {
+            match &event {
+                E::Str(val) => self.record_distinct_value(val),
+                E::OwnedStr(val) => self.record_distinct_value(val),
+                _ => {}
             }
-            (ev, ty) => fail!("Cannot accept event {ev} for primitive type {ty}"),
+        }
+
+        if self.guess_dates && self.string_format != StringFormat::Other {
+            let format = match &event {
+                E::Str(val) => StringFormat::guess(val),
+                E::OwnedStr(val) => StringFormat::guess(val),
+                _ => StringFormat::Other,
+            };
+            self.string_format = self.string_format.merge(format);
         }
         Ok(())
     }
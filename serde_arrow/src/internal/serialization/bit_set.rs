@@ -1,29 +1,33 @@
+/// A set of field indices, that grows as required
 #[derive(Debug, Clone, PartialEq, Default)]
-pub struct BitSet(u128);
+pub struct BitSet(Vec<u128>);
 
 impl BitSet {
-    pub const MAX: usize = u128::BITS as usize;
+    const BITS: usize = u128::BITS as usize;
 
     pub fn clear(&mut self) {
-        self.0 = 0;
+        self.0.iter_mut().for_each(|word| *word = 0);
     }
 
     pub fn insert(&mut self, field_idx: usize) {
-        if field_idx >= Self::MAX {
-            panic!("Attempt to insert an index beyond the maximum");
+        let (word, flag) = Self::position(field_idx);
+        if self.0.len() <= word {
+            self.0.resize(word + 1, 0);
         }
-        let flag = 1 << field_idx;
-        self.0 |= flag;
+        self.0[word] |= flag;
     }
 
     pub fn contains(&self, field_idx: usize) -> bool {
-        if field_idx >= Self::MAX {
-            false
-        } else {
-            let flag = 1 << field_idx;
-            (self.0 & flag) == flag
+        let (word, flag) = Self::position(field_idx);
+        match self.0.get(word) {
+            Some(word) => (word & flag) == flag,
+            None => false,
         }
     }
+
+    fn position(field_idx: usize) -> (usize, u128) {
+        (field_idx / Self::BITS, 1 << (field_idx % Self::BITS))
+    }
 }
 
 #[cfg(test)]
@@ -46,4 +50,17 @@ mod test {
         assert_eq!(true, set.contains(2));
         assert_eq!(false, set.contains(3));
     }
+
+    #[test]
+    fn large_indices() {
+        let mut set = BitSet::default();
+        set.insert(300);
+
+        assert!(set.contains(300));
+        assert!(!set.contains(172));
+        assert!(!set.contains(1000));
+
+        set.clear();
+        assert!(!set.contains(300));
+    }
 }
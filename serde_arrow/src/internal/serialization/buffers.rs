@@ -1,4 +1,4 @@
-use crate::internal::error::Result;
+use crate::internal::error::{fail, Result};
 
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct BitBuffer {
@@ -52,19 +52,35 @@ impl NullBuffer {
     }
 }
 
-pub trait Offset: std::ops::Add<Self, Output = Self> + Clone + Default {
-    fn try_form_usize(val: usize) -> Result<Self>;
+pub trait Offset: Clone + Default {
+    /// Add `val` to the offset, fail if the result is not representable
+    fn try_add_usize(&self, val: usize) -> Result<Self>;
 }
 
 impl Offset for i32 {
-    fn try_form_usize(val: usize) -> Result<Self> {
-        Ok(i32::try_from(val)?)
+    fn try_add_usize(&self, val: usize) -> Result<Self> {
+        match i32::try_from(val)
+            .ok()
+            .and_then(|val| self.checked_add(val))
+        {
+            Some(res) => Ok(res),
+            None => fail!(concat!(
+                "Offset overflow: the data exceeds the range of 32 bit offsets. ",
+                "Consider using LargeUtf8 or LargeList fields with 64 bit offsets",
+            )),
+        }
     }
 }
 
 impl Offset for i64 {
-    fn try_form_usize(val: usize) -> Result<Self> {
-        Ok(i64::try_from(val)?)
+    fn try_add_usize(&self, val: usize) -> Result<Self> {
+        match i64::try_from(val)
+            .ok()
+            .and_then(|val| self.checked_add(val))
+        {
+            Some(res) => Ok(res),
+            None => fail!("Offset overflow: the data exceeds the range of 64 bit offsets"),
+        }
     }
 }
 
@@ -92,7 +108,7 @@ impl<O: Offset> OffsetBuilder<O> {
 
     // push a new item with the given number of children
     pub fn push(&mut self, num_children: usize) -> Result<()> {
-        self.current_items = self.current_items.clone() + O::try_form_usize(num_children)?;
+        self.current_items = self.current_items.try_add_usize(num_children)?;
         self.offsets.push(self.current_items.clone());
 
         Ok(())
@@ -103,7 +119,7 @@ impl<O: Offset> OffsetBuilder<O> {
     }
 
     pub fn inc_current_items(&mut self) -> Result<()> {
-        self.current_items = self.current_items.clone() + O::try_form_usize(1)?;
+        self.current_items = self.current_items.try_add_usize(1)?;
         Ok(())
     }
 
@@ -111,3 +127,32 @@ impl<O: Offset> OffsetBuilder<O> {
         *self = Self::default();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::OffsetBuilder;
+
+    #[test]
+    fn i32_offset_overflow() {
+        let mut offsets = OffsetBuilder::<i32>::default();
+        offsets.push(i32::MAX as usize - 1).unwrap();
+        offsets.push(1).unwrap();
+
+        let err = offsets.push(1).unwrap_err();
+        assert!(err.to_string().contains("32 bit offsets"), "{err}");
+
+        let err = offsets.inc_current_items().unwrap_err();
+        assert!(err.to_string().contains("32 bit offsets"), "{err}");
+    }
+
+    #[test]
+    fn i64_offsets_exceed_i32() {
+        let mut offsets = OffsetBuilder::<i64>::default();
+        offsets.push(i32::MAX as usize).unwrap();
+        offsets.push(1).unwrap();
+        assert_eq!(
+            offsets.offsets,
+            vec![0, i32::MAX as i64, i32::MAX as i64 + 1]
+        );
+    }
+}
@@ -3,15 +3,13 @@ use std::collections::BTreeMap;
 use crate::{
     internal::{
         error::{error, fail},
-        schema::{GenericDataType, GenericField},
+        schema::{GenericDataType, GenericField, UntaggedShape},
         CONFIGURATION,
     },
     schema::Strategy,
     Result,
 };
 
-use super::bit_set::BitSet;
-
 const UNSET_INSTR: usize = usize::MAX;
 
 pub fn compile_serialization(
@@ -31,15 +29,35 @@ pub fn compile_serialization(
     Ok(program)
 }
 
+/// Configure how Rust values are serialized into arrays
+///
+/// Per default, nullable fields of the schema that are missing in the Rust
+/// values, also inside nested structs, are filled with nulls. This allows to
+/// serialize existing Rust types into schemas that gained nullable columns.
+///
 #[derive(Debug, Clone)]
 pub struct CompilationOptions {
+    /// If `true`, the records are wrapped in a struct, i.e., each field
+    /// becomes a separate array
     pub wrap_with_struct: bool,
+    /// If `true`, fields of the Rust values not present in the schema are
+    /// ignored. Otherwise, unknown fields result in an error
+    pub ignore_unknown_fields: bool,
+    /// If `true`, missing non-nullable fields of structs are filled with the
+    /// default value of their type. Otherwise, they result in an error
+    pub fill_missing_with_defaults: bool,
+    /// If `true`, numeric fields additionally accept integers in float fields
+    /// and booleans, see [`CompilationOptions::coerce_numbers`]
+    pub coerce_numbers: bool,
 }
 
 impl std::default::Default for CompilationOptions {
     fn default() -> Self {
         Self {
             wrap_with_struct: true,
+            ignore_unknown_fields: false,
+            fill_missing_with_defaults: false,
+            coerce_numbers: false,
         }
     }
 }
@@ -49,6 +67,53 @@ impl CompilationOptions {
         self.wrap_with_struct = value;
         self
     }
+
+    /// Configure whether to ignore fields not present in the schema
+    ///
+    /// This allows to serialize Rust types into schemas that dropped some of
+    /// their columns.
+    ///
+    pub fn ignore_unknown_fields(mut self, value: bool) -> Self {
+        self.ignore_unknown_fields = value;
+        self
+    }
+
+    /// Configure whether to fill missing non-nullable fields with defaults
+    ///
+    /// If enabled, non-nullable fields of structs and maps serialized as
+    /// structs that are missing in a row are filled with the default value of
+    /// their type: `0` for numbers, `false` for booleans, empty strings, empty
+    /// lists and empty maps. Nullable children of defaulted structs are set to
+    /// null. Unions and non-nullable dictionaries are not supported and still
+    /// result in an error.
+    ///
+    /// The rows filled per field can be queried with the `defaulted_fields`
+    /// methods of the `ArraysBuilder` types.
+    ///
+    pub fn fill_missing_with_defaults(mut self, value: bool) -> Self {
+        self.fill_missing_with_defaults = value;
+        self
+    }
+
+    /// Configure whether to coerce numbers into the types of numeric fields
+    ///
+    /// Per default, integer fields accept any integer that fits into their
+    /// type and float fields accept any float. If enabled, numeric fields in
+    /// addition
+    ///
+    /// - accept integers in float fields. The conversion may lose precision
+    /// - accept booleans, converted to `1` / `0`
+    /// - check the range of floats written into `F16` and `F32` fields
+    ///   instead of converting them to infinity
+    ///
+    /// Values outside the range of the field type result in an error naming
+    /// the field path and the value. Floats are still not accepted by integer
+    /// fields.
+    ///
+    pub fn coerce_numbers(mut self, value: bool) -> Self {
+        self.coerce_numbers = value;
+        self
+    }
 }
 
 trait Counter {
@@ -135,7 +200,9 @@ define_bytecode!(
     },
     ProgramEnd {},
     OuterSequenceStart {},
-    OuterRecordStart {},
+    OuterRecordStart {
+        seen: usize,
+    },
     LargeListStart {},
     ListStart {},
     MapStart {},
@@ -182,6 +249,23 @@ define_bytecode!(
     PushBool {
         idx: usize,
     },
+    PushCoercedNumber {
+        idx: usize,
+        /// The data type of the target array
+        data_type: GenericDataType,
+        /// The path of the field used in error messages
+        path: String,
+        /// If true, strings are parsed into numbers
+        from_str: bool,
+    },
+    PushBoolFromStr {
+        idx: usize,
+        /// The path of the field used in error messages
+        path: String,
+    },
+    PushDate32FromNaiveStr {
+        idx: usize,
+    },
     PushDate64FromNaiveStr {
         idx: usize,
     },
@@ -205,9 +289,11 @@ define_bytecode!(
     OuterRecordField {
         struct_idx: usize,
         field_name: String,
+        seen: usize,
     },
     OuterRecordEnd {
         struct_idx: usize,
+        seen: usize,
     },
     LargeListItem {
         list_idx: usize,
@@ -242,6 +328,7 @@ define_bytecode!(
         struct_idx: usize,
         seen: usize,
     },
+    StructUnitVariantKey {},
     MapItem {
         map_idx: usize,
         offsets: usize,
@@ -262,11 +349,28 @@ define_bytecode!(
         union_idx: usize,
         type_idx: usize,
     },
+    UntaggedVariant {
+        union_idx: usize,
+        type_idx: usize,
+    },
     PushDictionary {
         values: DictionaryValue,
         indices: DictionaryIndex,
         dictionary: usize,
     },
+    PushFormattedUtf8 {
+        buffer: usize,
+        offsets: usize,
+    },
+    PushFormattedLargeUtf8 {
+        buffer: usize,
+        offsets: usize,
+    },
+    PushFormattedDictionary {
+        values: DictionaryValue,
+        indices: DictionaryIndex,
+        dictionary: usize,
+    },
 );
 
 impl Bytecode {
@@ -291,6 +395,11 @@ pub struct StructDefinition {
     pub item: usize,
     /// The jump target if a struct is closed
     pub r#return: usize,
+    /// The index of the end instruction of the struct
+    ///
+    /// The return target may be moved past further instructions, e.g., the
+    /// end of a union, if the struct is its last variant.
+    pub end: usize,
 }
 
 /// Definition of a field inside a struct
@@ -302,6 +411,10 @@ pub struct FieldDefinition {
     pub jump: usize,
     /// The null definition for this field
     pub null_definition: Option<usize>,
+    /// The default definition for non-nullable fields
+    pub default_definition: Option<usize>,
+    /// The jump target for unit variant keys of maps serialized as structs
+    pub unit_variant_key: Option<usize>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -324,6 +437,17 @@ pub struct MapDefinition {
 #[derive(Default, Debug, Clone, PartialEq)]
 pub struct UnionDefinition {
     pub fields: Vec<usize>,
+    /// The shapes accepted by the fields of untagged unions
+    pub shapes: Vec<UntaggedShape>,
+}
+
+/// The buffers to fill for a missing non-nullable field
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DefaultDefinition {
+    /// The path of the field, e.g., `$.a.b`
+    pub path: String,
+    /// The null definition describing the affected buffers
+    pub null_definition: usize,
 }
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -433,6 +557,12 @@ impl NullDefinition {
                 self.u64_offsets.push(offsets);
                 self.u1.extend(validity);
             }
+            &ArrayMapping::Date32 {
+                buffer, validity, ..
+            } => {
+                self.u32.push(buffer);
+                self.u1.extend(validity);
+            }
             &ArrayMapping::Date64 {
                 buffer, validity, ..
             } => {
@@ -451,7 +581,14 @@ impl NullDefinition {
                 offsets, validity, ..
             } => {
                 // NOTE: the entries is not included
-                self.u64_offsets.push(offsets);
+                self.u32_offsets.push(offsets);
+                self.u1.extend(validity);
+            }
+            &ArrayMapping::List {
+                offsets, validity, ..
+            } => {
+                // NOTE: the item is not included
+                self.u32_offsets.push(offsets);
                 self.u1.extend(validity);
             }
             &ArrayMapping::LargeList {
@@ -572,6 +709,11 @@ pub enum ArrayMapping {
         offsets: usize,
         validity: Option<usize>,
     },
+    Date32 {
+        field: GenericField,
+        buffer: usize,
+        validity: Option<usize>,
+    },
     Date64 {
         field: GenericField,
         buffer: usize,
@@ -619,6 +761,8 @@ pub struct Program {
     pub(crate) options: CompilationOptions,
     pub(crate) structure: Structure,
     pub(crate) buffers: BufferCounts,
+    /// The names of the fields currently being compiled
+    path: Vec<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -631,6 +775,7 @@ pub struct Structure {
     pub structs: Vec<StructDefinition>,
     pub unions: Vec<UnionDefinition>,
     pub nulls: Vec<NullDefinition>,
+    pub defaults: Vec<DefaultDefinition>,
     pub array_mapping: Vec<ArrayMapping>,
 }
 
@@ -657,6 +802,8 @@ pub struct BufferCounts {
     pub(crate) num_dictionaries: usize,
     /// number of bit-sets to record seen / unseen fields
     pub(crate) num_seen: usize,
+    /// number of counters for fields filled with defaults
+    pub(crate) num_defaults: usize,
 }
 
 impl Program {
@@ -665,6 +812,7 @@ impl Program {
             options,
             structure: Structure::default(),
             buffers: BufferCounts::default(),
+            path: vec![String::from("$")],
         }
     }
 }
@@ -699,9 +847,14 @@ impl Program {
         });
         self.structure.large_lists[0].item = self.structure.program.len();
 
+        let mut seen = usize::MAX;
         if self.options.wrap_with_struct {
+            seen = self.buffers.num_seen.next_value();
             self.structure.structs.push(StructDefinition::default());
-            self.push_instr(OuterRecordStart { next: UNSET_INSTR });
+            self.push_instr(OuterRecordStart {
+                next: UNSET_INSTR,
+                seen,
+            });
         }
 
         for (field_idx, field) in fields.iter().enumerate() {
@@ -710,6 +863,7 @@ impl Program {
                     next: UNSET_INSTR,
                     struct_idx: 0,
                     field_name: field.name.to_string(),
+                    seen,
                 });
                 self.structure.structs[0].fields.insert(
                     field.name.to_string(),
@@ -717,18 +871,32 @@ impl Program {
                         index: field_idx,
                         jump: self.structure.program.len(),
                         null_definition: None,
+                        default_definition: None,
+                        unit_variant_key: None,
                     },
                 );
             }
-            let (f, _) = self.compile_field(field)?;
+            let (f, null_definition) = self.compile_field(field)?;
+
+            if self.options.wrap_with_struct {
+                let default_definition = self.compile_default(field, &f)?;
+                let field_def = self.structure.structs[0]
+                    .fields
+                    .get_mut(&field.name)
+                    .ok_or_else(|| error!("compile error: could not read record field"))?;
+                field_def.null_definition = null_definition;
+                field_def.default_definition = default_definition;
+            }
 
             self.structure.array_mapping.push(f);
         }
 
         if self.options.wrap_with_struct {
+            self.structure.structs[0].end = self.structure.program.len();
             self.push_instr(OuterRecordEnd {
                 next: UNSET_INSTR,
                 struct_idx: 0,
+                seen,
             });
             self.structure.structs[0].r#return = self.structure.program.len();
         }
@@ -786,17 +954,21 @@ impl Program {
 
         for (field_idx, field) in field.children.iter().enumerate() {
             if !is_tuple {
-                if field_idx >= BitSet::MAX {
-                    fail!("Structs can contain at most {} fields", BitSet::MAX);
-                }
+                let mut unit_variant_key = None;
                 if is_map {
+                    // layout: item, unit variant key, field, value. Both the
+                    // item and the unit variant key skip the next instruction
+                    let pos = self.structure.program.len();
                     self.push_instr(StructItem {
-                        next: UNSET_INSTR,
+                        next: pos + 2,
                         seen,
                         struct_idx,
                     });
+                    self.push_instr(StructUnitVariantKey { next: pos + 3 });
+                    unit_variant_key = Some(pos + 1);
+
                     if self.structure.structs[struct_idx].item == UNSET_INSTR {
-                        self.structure.structs[struct_idx].item = self.structure.program.len();
+                        self.structure.structs[struct_idx].item = pos + 2;
                     }
                 }
                 self.push_instr(StructField {
@@ -812,6 +984,8 @@ impl Program {
                         index: field_idx,
                         jump: self.structure.program.len(),
                         null_definition: None,
+                        default_definition: None,
+                        unit_variant_key,
                     },
                 );
             } else {
@@ -820,17 +994,20 @@ impl Program {
             let (f, null_definition) = self.compile_field(field)?;
 
             if !is_tuple {
+                let default_definition = self.compile_default(field, &f)?;
                 let field_def = self.structure.structs[struct_idx]
                     .fields
                     .get_mut(&field.name)
                     .ok_or_else(|| error!("compile error: could not read struct field"))?;
                 field_def.null_definition = null_definition;
+                field_def.default_definition = default_definition;
             }
 
             field_mapping.push(f);
         }
 
         if !is_tuple {
+            self.structure.structs[struct_idx].end = self.structure.program.len();
             self.push_instr(StructEnd {
                 next: UNSET_INSTR,
                 struct_idx,
@@ -958,11 +1135,28 @@ impl Program {
         let mut fields = Vec::new();
         let mut child_last_instr = Vec::new();
 
-        self.push_instr(Variant {
-            next: UNSET_INSTR,
-            union_idx,
-            type_idx,
-        });
+        if matches!(field.strategy, Some(Strategy::UntaggedUnion)) {
+            for child in &field.children {
+                let Some(shape) = UntaggedShape::from_field(child) else {
+                    fail!(
+                        "cannot compile untagged union with child of type {}",
+                        child.data_type
+                    );
+                };
+                self.structure.unions[union_idx].shapes.push(shape);
+            }
+            self.push_instr(UntaggedVariant {
+                next: UNSET_INSTR,
+                union_idx,
+                type_idx,
+            });
+        } else {
+            self.push_instr(Variant {
+                next: UNSET_INSTR,
+                union_idx,
+                type_idx,
+            });
+        }
 
         for (child_idx, child) in field.children.iter().enumerate() {
             self.structure.unions[union_idx]
@@ -1021,6 +1215,16 @@ impl Program {
     /// definition index
     ///
     fn compile_field(&mut self, field: &GenericField) -> Result<(ArrayMapping, Option<usize>)> {
+        self.path.push(field.name.to_string());
+        let res = self.compile_field_with_null_check(field)?;
+        self.path.pop();
+        Ok(res)
+    }
+
+    fn compile_field_with_null_check(
+        &mut self,
+        field: &GenericField,
+    ) -> Result<(ArrayMapping, Option<usize>)> {
         let mut option_marker_pos = None;
         let validity = if self.requires_null_check(field) {
             let validity = self.buffers.num_u1.next_value();
@@ -1047,7 +1251,8 @@ impl Program {
 
         if let Some(option_marker_pos) = option_marker_pos {
             let current_program_len = self.structure.program.len();
-            let Bytecode::OptionMarker(instr) = &mut self.structure.program[option_marker_pos] else {
+            let Bytecode::OptionMarker(instr) = &mut self.structure.program[option_marker_pos]
+            else {
                 fail!("Internal error during compilation");
             };
             instr.if_none = current_program_len;
@@ -1061,6 +1266,36 @@ impl Program {
         }
     }
 
+    /// compile the default definition of a non-nullable struct field, if
+    /// missing fields are filled with defaults
+    ///
+    fn compile_default(
+        &mut self,
+        field: &GenericField,
+        array_mapping: &ArrayMapping,
+    ) -> Result<Option<usize>> {
+        if !self.options.fill_missing_with_defaults
+            || field.nullable
+            || !supports_default(array_mapping)
+        {
+            return Ok(None);
+        }
+
+        let mut null_definition = NullDefinition::default();
+        null_definition.update_from_array_mapping(array_mapping)?;
+        null_definition.sort_indices();
+
+        let default_definition = self.structure.defaults.len();
+        self.structure.defaults.push(DefaultDefinition {
+            path: format!("{}.{}", self.path.join("."), field.name),
+            null_definition: self.structure.nulls.len(),
+        });
+        self.structure.nulls.push(null_definition);
+        self.buffers.num_defaults += 1;
+
+        Ok(Some(default_definition))
+    }
+
     fn requires_null_check(&self, field: &GenericField) -> bool {
         // NOTE: Null fields are handled via the PushNull primitive and do
         // not require additional null checks
@@ -1068,6 +1303,57 @@ impl Program {
     }
 }
 
+macro_rules! compile_coerced_number {
+    ($this:expr, $field:expr, $validity:expr, $from_str:expr, $num:ident, $mapping:ident) => {{
+        let idx = $this.buffers.$num.next_value();
+        $this.push_instr(PushCoercedNumber {
+            next: UNSET_INSTR,
+            idx,
+            data_type: $field.data_type,
+            path: $this.path.join("."),
+            from_str: $from_str,
+        });
+        Ok(Some(ArrayMapping::$mapping {
+            field: $field.clone(),
+            buffer: idx,
+            validity: $validity,
+        }))
+    }};
+}
+
+impl Program {
+    /// compile numeric fields that accept any number, if numbers are coerced,
+    /// the field was traced from inconsistent types, or the field uses the
+    /// `StrAsNumber` strategy
+    ///
+    /// Returns `None` for non-numeric fields.
+    ///
+    fn compile_coerced_number(
+        &mut self,
+        field: &GenericField,
+        validity: Option<usize>,
+    ) -> Result<Option<ArrayMapping>> {
+        use GenericDataType as D;
+
+        let from_str = matches!(field.strategy, Some(Strategy::StrAsNumber));
+
+        match field.data_type {
+            D::U8 => compile_coerced_number!(self, field, validity, from_str, num_u8, U8),
+            D::U16 => compile_coerced_number!(self, field, validity, from_str, num_u16, U16),
+            D::U32 => compile_coerced_number!(self, field, validity, from_str, num_u32, U32),
+            D::U64 => compile_coerced_number!(self, field, validity, from_str, num_u64, U64),
+            D::I8 => compile_coerced_number!(self, field, validity, from_str, num_u8, I8),
+            D::I16 => compile_coerced_number!(self, field, validity, from_str, num_u16, I16),
+            D::I32 => compile_coerced_number!(self, field, validity, from_str, num_u32, I32),
+            D::I64 => compile_coerced_number!(self, field, validity, from_str, num_u64, I64),
+            D::F16 => compile_coerced_number!(self, field, validity, from_str, num_u16, F16),
+            D::F32 => compile_coerced_number!(self, field, validity, from_str, num_u32, F32),
+            D::F64 => compile_coerced_number!(self, field, validity, from_str, num_u64, F64),
+            _ => Ok(None),
+        }
+    }
+}
+
 macro_rules! compile_primtive {
     ($this:expr, $field:expr, $validity:expr, $num:ident, $instr:ident, $mapping:ident) => {{
         $this.push_instr($instr {
@@ -1093,9 +1379,36 @@ impl Program {
     ) -> Result<ArrayMapping> {
         use GenericDataType as D;
 
+        let coerce_number = match field.strategy.as_ref() {
+            Some(Strategy::StrAsNumber | Strategy::InconsistentTypes) => true,
+            Some(_) => false,
+            None => self.options.coerce_numbers,
+        };
+        if coerce_number {
+            if let Some(array_mapping) = self.compile_coerced_number(field, validity)? {
+                return Ok(array_mapping);
+            }
+        }
+
         match field.data_type {
             D::Null => compile_primtive!(self, field, validity, num_u0, PushNull, Null),
-            D::Bool => compile_primtive!(self, field, validity, num_u1, PushBool, Bool),
+            D::Bool => match field.strategy.as_ref() {
+                Some(Strategy::StrAsBool) => {
+                    let idx = self.buffers.num_u1.next_value();
+                    self.push_instr(PushBoolFromStr {
+                        next: UNSET_INSTR,
+                        idx,
+                        path: self.path.join("."),
+                    });
+                    Ok(ArrayMapping::Bool {
+                        field: field.clone(),
+                        buffer: idx,
+                        validity,
+                    })
+                }
+                None => compile_primtive!(self, field, validity, num_u1, PushBool, Bool),
+                Some(strategy) => fail!("Cannot compile Bool with strategy {strategy}"),
+            },
             D::U8 => compile_primtive!(self, field, validity, num_u8, PushU8, U8),
             D::U16 => compile_primtive!(self, field, validity, num_u16, PushU16, U16),
             D::U32 => compile_primtive!(self, field, validity, num_u32, PushU32, U32),
@@ -1111,11 +1424,19 @@ impl Program {
                 let buffer = self.buffers.num_u8.next_value();
                 let offsets = self.buffers.num_u32_offsets.next_value();
 
-                self.push_instr(PushUtf8 {
-                    next: UNSET_INSTR,
-                    buffer,
-                    offsets,
-                });
+                if matches!(field.strategy, Some(Strategy::InconsistentTypes)) {
+                    self.push_instr(PushFormattedUtf8 {
+                        next: UNSET_INSTR,
+                        buffer,
+                        offsets,
+                    });
+                } else {
+                    self.push_instr(PushUtf8 {
+                        next: UNSET_INSTR,
+                        buffer,
+                        offsets,
+                    });
+                }
                 Ok(ArrayMapping::Utf8 {
                     field: field.clone(),
                     buffer,
@@ -1127,11 +1448,19 @@ impl Program {
                 let buffer = self.buffers.num_u8.next_value();
                 let offsets = self.buffers.num_u64_offsets.next_value();
 
-                self.push_instr(PushLargeUtf8 {
-                    next: UNSET_INSTR,
-                    buffer,
-                    offsets,
-                });
+                if matches!(field.strategy, Some(Strategy::InconsistentTypes)) {
+                    self.push_instr(PushFormattedLargeUtf8 {
+                        next: UNSET_INSTR,
+                        buffer,
+                        offsets,
+                    });
+                } else {
+                    self.push_instr(PushLargeUtf8 {
+                        next: UNSET_INSTR,
+                        buffer,
+                        offsets,
+                    });
+                }
                 Ok(ArrayMapping::LargeUtf8 {
                     field: field.clone(),
                     buffer,
@@ -1139,6 +1468,18 @@ impl Program {
                     validity,
                 })
             }
+            D::Date32 => match field.strategy.as_ref() {
+                Some(Strategy::NaiveStrAsDate32) => compile_primtive!(
+                    self,
+                    field,
+                    validity,
+                    num_u32,
+                    PushDate32FromNaiveStr,
+                    Date32
+                ),
+                None => compile_primtive!(self, field, validity, num_u32, PushI32, Date32),
+                Some(strategy) => fail!("Cannot compile Date32 with strategy {strategy}"),
+            },
             D::Date64 => match field.strategy.as_ref() {
                 Some(Strategy::NaiveStrAsDate64) => compile_primtive!(
                     self,
@@ -1201,12 +1542,21 @@ impl Program {
         };
         let dictionary = self.buffers.num_dictionaries.next_value();
 
-        self.push_instr(PushDictionary {
-            next: UNSET_INSTR,
-            dictionary,
-            values,
-            indices,
-        });
+        if matches!(field.strategy, Some(Strategy::InconsistentTypes)) {
+            self.push_instr(PushFormattedDictionary {
+                next: UNSET_INSTR,
+                dictionary,
+                values,
+                indices,
+            });
+        } else {
+            self.push_instr(PushDictionary {
+                next: UNSET_INSTR,
+                dictionary,
+                values,
+                indices,
+            });
+        }
 
         Ok(M::Dictionary {
             field: field.clone(),
@@ -1347,6 +1697,7 @@ impl Program {
                 Some(Bytecode::ListEnd(_))
                     | Some(Bytecode::LargeListEnd(_))
                     | Some(Bytecode::OuterSequenceEnd(_))
+                    | Some(Bytecode::UnionEnd(_))
             ) {
                 fail!("invalid {label} definition ({list_idx}): instr before return is {before_return_instr:?}");
             }
@@ -1384,10 +1735,30 @@ impl Program {
                 fail!("invalid struct definition ({struct_idx}): return jumps to invalid target");
             }
 
+            let end_instr = &self.structure.program[r#struct.end];
+            let is_valid = match end_instr {
+                Bytecode::StructEnd(instr) => instr.struct_idx == struct_idx,
+                Bytecode::OuterRecordEnd(instr) => instr.struct_idx == struct_idx,
+                _ => false,
+            };
+            if !is_valid {
+                fail!("invalid struct definition ({struct_idx}): end points to {end_instr:?}");
+            }
+
             for (name, field_def) in &r#struct.fields {
                 if !self.structure.program[field_def.jump].is_allowed_jump_target() {
                     fail!("invalid struct definition ({struct_idx}): field jump {name} to invalid target");
                 }
+                if let Some(unit_variant_key) = field_def.unit_variant_key {
+                    let instr = &self.structure.program[unit_variant_key];
+                    let is_valid = matches!(
+                        instr,
+                        Bytecode::StructUnitVariantKey(instr) if instr.next == field_def.jump
+                    );
+                    if !is_valid {
+                        fail!("invalid struct definition ({struct_idx}): unit variant key of field {name} is {instr:?}");
+                    }
+                }
             }
         }
         Ok(())
@@ -1519,3 +1890,18 @@ impl Program {
         Ok(())
     }
 }
+
+/// Check whether the default value of an array can be expressed by padding its
+/// buffers
+///
+/// Unions and non-nullable dictionaries would require valid type ids or
+/// dictionary entries and are not supported.
+///
+fn supports_default(array_mapping: &ArrayMapping) -> bool {
+    match array_mapping {
+        ArrayMapping::Union { .. } => false,
+        ArrayMapping::Dictionary { validity, .. } => validity.is_some(),
+        ArrayMapping::Struct { fields, .. } => fields.iter().all(supports_default),
+        _ => true,
+    }
+}
@@ -1,22 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+
+use half::f16;
 
 use crate::{
     base::Event,
     internal::{
-        conversions::{ToBytes, WrappedF16, WrappedF32, WrappedF64},
-        error::{self, fail, Result},
+        conversions::{ToBytes, WrappedF16, WrappedF32, WrappedF64, UNIX_EPOCH_DAYS_FROM_CE},
+        error::{error, fail, Error, Result},
+        schema::{GenericDataType, UntaggedShape},
         serialization::{
             buffers::{BitBuffer, NullBuffer, OffsetBuilder},
             compiler::{
-                dispatch_bytecode, BufferCounts, Bytecode, DictionaryIndex, DictionaryValue,
-                LargeListEnd, LargeListItem, LargeListStart, ListEnd, ListItem, ListStart, MapEnd,
-                MapItem, MapStart, OptionMarker, OuterRecordEnd, OuterRecordField,
-                OuterRecordStart, OuterSequenceEnd, OuterSequenceItem, OuterSequenceStart, Program,
-                ProgramEnd, PushBool, PushDate64FromNaiveStr, PushDate64FromUtcStr, PushDictionary,
-                PushF32, PushF64, PushI16, PushI32, PushI64, PushI8, PushLargeUtf8, PushNull,
-                PushU16, PushU32, PushU64, PushU8, PushUtf8, StructEnd, StructField, StructItem,
-                StructStart, Structure, TupleStructEnd, TupleStructItem, TupleStructStart,
-                UnionEnd, Variant,
+                dispatch_bytecode, BufferCounts, Bytecode, CompilationOptions, DictionaryIndex,
+                DictionaryValue, LargeListEnd, LargeListItem, LargeListStart, ListEnd, ListItem,
+                ListStart, MapEnd, MapItem, MapStart, OptionMarker, OuterRecordEnd,
+                OuterRecordField, OuterRecordStart, OuterSequenceEnd, OuterSequenceItem,
+                OuterSequenceStart, Program, ProgramEnd, PushBool, PushBoolFromStr,
+                PushCoercedNumber, PushDate32FromNaiveStr, PushDate64FromNaiveStr,
+                PushDate64FromUtcStr, PushDictionary, PushF32, PushF64, PushFormattedDictionary,
+                PushFormattedLargeUtf8, PushFormattedUtf8, PushI16, PushI32, PushI64, PushI8,
+                PushLargeUtf8, PushNull, PushU16, PushU32, PushU64, PushU8, PushUtf8, StructEnd,
+                StructField, StructItem, StructStart, StructUnitVariantKey, Structure,
+                TupleStructEnd, TupleStructItem, TupleStructStart, UnionEnd, UntaggedVariant,
+                Variant,
             },
         },
         sink::EventSink,
@@ -30,6 +36,20 @@ pub struct Interpreter {
     pub program_counter: usize,
     pub structure: Structure,
     pub buffers: Buffers,
+    pub options: CompilationOptions,
+    /// The state of the value of an unknown field currently being skipped
+    skipped: Option<SkippedValue>,
+}
+
+/// Track the events of a value that is ignored by the interpreter
+#[derive(Debug, Clone)]
+struct SkippedValue {
+    /// The nesting depth inside the skipped value
+    depth: usize,
+    /// The number of complete values still to skip
+    remaining: usize,
+    /// The instruction to continue with once the value was skipped
+    r#return: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -54,6 +74,8 @@ pub struct Buffers {
     pub seen: Vec<BitSet>,
     /// mappings from strings to indices for dictionaries
     pub dictionaries: Vec<HashMap<String, usize>>,
+    /// number of rows filled with defaults per default definition
+    pub defaulted: Vec<usize>,
 }
 
 impl Buffers {
@@ -69,6 +91,7 @@ impl Buffers {
             u64_offsets: vec![Default::default(); counts.num_u64_offsets],
             seen: vec![Default::default(); counts.num_seen],
             dictionaries: vec![Default::default(); counts.num_dictionaries],
+            defaulted: vec![0; counts.num_defaults],
         }
     }
 
@@ -83,6 +106,7 @@ impl Buffers {
         self.u64_offsets.iter_mut().for_each(|b| b.clear());
         self.seen.iter_mut().for_each(|b| b.clear());
         self.dictionaries.iter_mut().for_each(|b| b.clear());
+        self.defaulted.iter_mut().for_each(|c| *c = 0);
     }
 }
 
@@ -92,7 +116,109 @@ impl Interpreter {
             program_counter: 0,
             structure: program.structure,
             buffers: Buffers::from_counts(&program.buffers),
+            options: program.options,
+            skipped: None,
+        }
+    }
+
+    /// The number of rows per field path filled with defaults
+    ///
+    /// Only fields with at least one defaulted row are included. The counts
+    /// are reset, when the arrays are built.
+    ///
+    pub fn defaulted_fields(&self) -> BTreeMap<String, usize> {
+        let mut res = BTreeMap::new();
+        for (default_definition, &count) in self.buffers.defaulted.iter().enumerate() {
+            if count != 0 {
+                let path = &self.structure.defaults[default_definition].path;
+                *res.entry(path.clone()).or_default() += count;
+            }
+        }
+        res
+    }
+
+    /// Start skipping the value of a struct field not present in the schema
+    ///
+    /// Returns `true` if the current instruction expects a struct field, the
+    /// field is unknown and unknown fields are ignored. `values` is the number
+    /// of complete values to skip, e.g., 2 for unit variant keys of maps
+    /// serialized as structs, which are followed by a null.
+    ///
+    fn skip_unknown_field(&mut self, key: &str, values: usize) -> bool {
+        let Some(struct_idx) = self.unknown_field_struct() else {
+            return false;
+        };
+        let struct_def = &self.structure.structs[struct_idx];
+        if struct_def.fields.contains_key(key) {
+            return false;
+        }
+
+        // NOTE: the end instruction of a struct accepts further fields
+        self.skipped = Some(SkippedValue {
+            depth: 0,
+            remaining: values,
+            r#return: struct_def.end,
+        });
+        true
+    }
+
+    /// Start skipping the value of a struct field with a non-string key
+    ///
+    /// See [`skip_unknown_field`][Self::skip_unknown_field]. The key is only
+    /// formatted, if the current instruction expects a struct field.
+    ///
+    fn skip_unknown_formatted_field<K: ToString>(&mut self, key: &K, values: usize) -> bool {
+        if self.unknown_field_struct().is_none() {
+            return false;
+        }
+        self.skip_unknown_field(&key.to_string(), values)
+    }
+
+    /// The struct expecting a field, if unknown fields are ignored
+    ///
+    fn unknown_field_struct(&self) -> Option<usize> {
+        if !self.options.ignore_unknown_fields || self.skipped.is_some() {
+            return None;
         }
+        match &self.structure.program[self.program_counter] {
+            Bytecode::StructField(instr) => Some(instr.struct_idx),
+            Bytecode::StructEnd(instr) => Some(instr.struct_idx),
+            Bytecode::OuterRecordField(instr) => Some(instr.struct_idx),
+            Bytecode::OuterRecordEnd(instr) => Some(instr.struct_idx),
+            _ => None,
+        }
+    }
+
+    fn skip(&mut self, event: Event<'_>) -> Result<()> {
+        let Some(skipped) = self.skipped.as_mut() else {
+            fail!("internal error: no value is being skipped");
+        };
+
+        match event {
+            Event::StartSequence | Event::StartTuple | Event::StartMap | Event::StartStruct => {
+                skipped.depth += 1;
+                return Ok(());
+            }
+            Event::EndSequence | Event::EndTuple | Event::EndMap | Event::EndStruct => {
+                let Some(depth) = skipped.depth.checked_sub(1) else {
+                    fail!("unbalanced {event} while skipping the value of an unknown field");
+                };
+                skipped.depth = depth;
+            }
+            Event::Item | Event::Some | Event::Variant(_, _) | Event::OwnedVariant(_, _) => {
+                return Ok(());
+            }
+            _ => {}
+        }
+
+        if skipped.depth == 0 {
+            skipped.remaining -= 1;
+            if skipped.remaining == 0 {
+                self.program_counter = skipped.r#return;
+                self.skipped = None;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -269,7 +395,8 @@ impl Instruction for OuterSequenceEnd {
 }
 
 impl Instruction for OuterRecordStart {
-    fn accept_start_struct(&self, _structure: &Structure, _buffers: &mut Buffers) -> Result<usize> {
+    fn accept_start_struct(&self, _structure: &Structure, buffers: &mut Buffers) -> Result<usize> {
+        buffers.seen[self.seen].clear();
         Ok(self.next)
     }
 
@@ -278,7 +405,8 @@ impl Instruction for OuterRecordStart {
     }
 }
 impl Instruction for OuterRecordField {
-    fn accept_end_struct(&self, structure: &Structure, _buffers: &mut Buffers) -> Result<usize> {
+    fn accept_end_struct(&self, structure: &Structure, buffers: &mut Buffers) -> Result<usize> {
+        struct_end(structure, buffers, self.struct_idx, self.seen)?;
         Ok(structure.structs[self.struct_idx].r#return)
     }
 
@@ -286,25 +414,27 @@ impl Instruction for OuterRecordField {
         self.accept_end_struct(structure, buffers)
     }
 
-    fn accept_str(
-        &self,
-        structure: &Structure,
-        _buffers: &mut Buffers,
-        val: &str,
-    ) -> Result<usize> {
+    fn accept_str(&self, structure: &Structure, buffers: &mut Buffers, val: &str) -> Result<usize> {
         if self.field_name == val {
+            let field_def = &structure.structs[self.struct_idx].fields[val];
+            buffers.seen[self.seen].insert(field_def.index);
             Ok(self.next)
         } else {
             let Some(field_def) = structure.structs[self.struct_idx].fields.get(val) else {
-                fail!("Cannot find field {val} in struct {idx}", idx=self.struct_idx);
+                fail!(
+                    "Cannot find field {val} in struct {idx}",
+                    idx = self.struct_idx
+                );
             };
+            buffers.seen[self.seen].insert(field_def.index);
             Ok(field_def.jump)
         }
     }
 }
 
 impl Instruction for OuterRecordEnd {
-    fn accept_end_struct(&self, _structure: &Structure, _buffers: &mut Buffers) -> Result<usize> {
+    fn accept_end_struct(&self, structure: &Structure, buffers: &mut Buffers) -> Result<usize> {
+        struct_end(structure, buffers, self.struct_idx, self.seen)?;
         Ok(self.next)
     }
 
@@ -312,15 +442,14 @@ impl Instruction for OuterRecordEnd {
         self.accept_end_struct(structure, buffers)
     }
 
-    fn accept_str(
-        &self,
-        structure: &Structure,
-        _buffers: &mut Buffers,
-        val: &str,
-    ) -> Result<usize> {
+    fn accept_str(&self, structure: &Structure, buffers: &mut Buffers, val: &str) -> Result<usize> {
         let Some(field_def) = structure.structs[self.struct_idx].fields.get(val) else {
-            fail!("cannot find field {val:?} in struct {idx}", idx=self.struct_idx);
+            fail!(
+                "cannot find field {val:?} in struct {idx}",
+                idx = self.struct_idx
+            );
         };
+        buffers.seen[self.seen].insert(field_def.index);
         Ok(field_def.jump)
     }
 }
@@ -428,11 +557,17 @@ fn struct_end(
     seen: usize,
 ) -> Result<()> {
     for (name, field_def) in &structure.structs[struct_idx].fields {
-        if !buffers.seen[seen].contains(field_def.index) {
-            let null_definition = field_def
-                .null_definition
-                .ok_or_else(|| error::error!("missing non-nullable field {name} in struct"))?;
+        if buffers.seen[seen].contains(field_def.index) {
+            continue;
+        }
+        if let Some(null_definition) = field_def.null_definition {
+            apply_null(structure, buffers, null_definition)?;
+        } else if let Some(default_definition) = field_def.default_definition {
+            let null_definition = structure.defaults[default_definition].null_definition;
             apply_null(structure, buffers, null_definition)?;
+            buffers.defaulted[default_definition] += 1;
+        } else {
+            fail!("missing non-nullable field {name} in struct");
         }
     }
     buffers.seen[seen].clear();
@@ -440,6 +575,45 @@ fn struct_end(
     Ok(())
 }
 
+/// Jump to the field of a map serialized as a struct with a non-string key
+fn struct_field_from_unit_variant(
+    structure: &Structure,
+    buffers: &mut Buffers,
+    struct_idx: usize,
+    seen: usize,
+    name: &str,
+) -> Result<usize> {
+    let Some(field_def) = structure.structs[struct_idx].fields.get(name) else {
+        fail!("cannot find field {name:?} in struct {struct_idx}");
+    };
+    let Some(unit_variant_key) = field_def.unit_variant_key else {
+        fail!("unit variant keys are only supported for maps serialized as structs");
+    };
+    buffers.seen[seen].insert(field_def.index);
+    Ok(unit_variant_key)
+}
+
+/// Format non-string keys of maps serialized as structs
+macro_rules! struct_key_handle {
+    ($($name:ident: $ty:ty),*) => {
+        $(
+            fn $name(&self, structure: &Structure, buffers: &mut Buffers, val: $ty) -> Result<usize> {
+                self.accept_str(structure, buffers, &val.to_string())
+            }
+        )*
+
+        fn accept_variant(
+            &self,
+            structure: &Structure,
+            buffers: &mut Buffers,
+            name: &str,
+            _idx: usize,
+        ) -> Result<usize> {
+            struct_field_from_unit_variant(structure, buffers, self.struct_idx, self.seen, name)
+        }
+    };
+}
+
 impl Instruction for StructStart {
     fn accept_start_struct(&self, _structure: &Structure, buffers: &mut Buffers) -> Result<usize> {
         buffers.seen[self.seen].clear();
@@ -467,12 +641,27 @@ impl Instruction for StructField {
             Ok(self.next)
         } else {
             let Some(field_def) = structure.structs[self.struct_idx].fields.get(val) else {
-                fail!("Cannot find field {val} in struct {idx}", idx=self.struct_idx);
+                fail!(
+                    "Cannot find field {val} in struct {idx}",
+                    idx = self.struct_idx
+                );
             };
             buffers.seen[self.seen].insert(field_def.index);
             Ok(field_def.jump)
         }
     }
+
+    struct_key_handle!(
+        accept_bool: bool,
+        accept_i8: i8,
+        accept_i16: i16,
+        accept_i32: i32,
+        accept_i64: i64,
+        accept_u8: u8,
+        accept_u16: u16,
+        accept_u32: u32,
+        accept_u64: u64
+    );
 }
 
 impl Instruction for StructEnd {
@@ -487,7 +676,10 @@ impl Instruction for StructEnd {
 
     fn accept_str(&self, structure: &Structure, buffers: &mut Buffers, val: &str) -> Result<usize> {
         let Some(field_def) = structure.structs[self.struct_idx].fields.get(val) else {
-            fail!("cannot find field {val:?} in struct {idx}", idx=self.struct_idx);
+            fail!(
+                "cannot find field {val:?} in struct {idx}",
+                idx = self.struct_idx
+            );
         };
         buffers.seen[self.seen].insert(field_def.index);
         Ok(field_def.jump)
@@ -497,6 +689,24 @@ impl Instruction for StructEnd {
     fn accept_item(&self, structure: &Structure, _buffers: &mut Buffers) -> Result<usize> {
         Ok(structure.structs[self.struct_idx].item)
     }
+
+    struct_key_handle!(
+        accept_bool: bool,
+        accept_i8: i8,
+        accept_i16: i16,
+        accept_i32: i32,
+        accept_i64: i64,
+        accept_u8: u8,
+        accept_u16: u16,
+        accept_u32: u32,
+        accept_u64: u64
+    );
+}
+
+impl Instruction for StructUnitVariantKey {
+    fn accept_null(&self, _structure: &Structure, _buffers: &mut Buffers) -> Result<usize> {
+        Ok(self.next)
+    }
 }
 
 impl Instruction for StructItem {
@@ -621,6 +831,89 @@ impl Instruction for Variant {
     }
 }
 
+impl UntaggedVariant {
+    fn select(
+        &self,
+        structure: &Structure,
+        buffers: &mut Buffers,
+        shape: UntaggedShape,
+    ) -> Result<usize> {
+        let union = &structure.unions[self.union_idx];
+        let Some(idx) = union.shapes.iter().position(|s| *s == shape) else {
+            fail!(
+                concat!(
+                    "Serialization failed: no variant of the untagged union accepts values of shape {shape}. ",
+                    "To fix this error, make sure all shapes are seen during schema tracing or add the relevant ",
+                    "variants manually to the traced fields.",
+                ),
+                shape = shape,
+            );
+        };
+        buffers.u8[self.type_idx].push(i8::try_from(idx)?.to_bytes());
+        Ok(union.fields[idx])
+    }
+}
+
+macro_rules! untagged_variant_handle {
+    ($name:ident, $shape:ident$(, $($val:ident: $ty:ty),*)?) => {
+        fn $name(&self, structure: &Structure, buffers: &mut Buffers $(, $($val: $ty),*)?) -> Result<usize> {
+            let next = self.select(structure, buffers, UntaggedShape::$shape)?;
+            dispatch_bytecode!(&structure.program[next], instr => instr.$name(structure, buffers $(, $($val),*)?))
+        }
+    };
+}
+
+/// Route values of untagged unions to the child matching their shape
+///
+/// The child is determined by the first event of the value. This event is
+/// then passed to the first instruction of the child inline.
+///
+impl Instruction for UntaggedVariant {
+    untagged_variant_handle!(accept_start_sequence, Sequence);
+    untagged_variant_handle!(accept_start_tuple, Tuple);
+    untagged_variant_handle!(accept_start_struct, Struct);
+    untagged_variant_handle!(accept_start_map, Map);
+    untagged_variant_handle!(accept_variant, Variant, name: &str, idx: usize);
+    untagged_variant_handle!(accept_bool, Bool, val: bool);
+    untagged_variant_handle!(accept_u8, U8, val: u8);
+    untagged_variant_handle!(accept_u16, U16, val: u16);
+    untagged_variant_handle!(accept_u32, U32, val: u32);
+    untagged_variant_handle!(accept_u64, U64, val: u64);
+    untagged_variant_handle!(accept_i8, I8, val: i8);
+    untagged_variant_handle!(accept_i16, I16, val: i16);
+    untagged_variant_handle!(accept_i32, I32, val: i32);
+    untagged_variant_handle!(accept_i64, I64, val: i64);
+    untagged_variant_handle!(accept_f32, F32, val: f32);
+    untagged_variant_handle!(accept_f64, F64, val: f64);
+    untagged_variant_handle!(accept_str, Str, val: &str);
+}
+
+/// Format primitive values as strings, used for fields coerced to strings
+macro_rules! format_primitive_handle {
+    () => {
+        format_primitive_handle!(
+            accept_bool: bool,
+            accept_u8: u8,
+            accept_u16: u16,
+            accept_u32: u32,
+            accept_u64: u64,
+            accept_i8: i8,
+            accept_i16: i16,
+            accept_i32: i32,
+            accept_i64: i64,
+            accept_f32: f32,
+            accept_f64: f64
+        );
+    };
+    ($($name:ident: $ty:ty),*) => {
+        $(
+            fn $name(&self, structure: &Structure, buffers: &mut Buffers, val: $ty) -> Result<usize> {
+                self.accept_str(structure, buffers, &val.to_string())
+            }
+        )*
+    };
+}
+
 impl Instruction for PushUtf8 {
     fn accept_str(
         &self,
@@ -647,6 +940,21 @@ impl Instruction for PushLargeUtf8 {
     }
 }
 
+impl Instruction for PushDate32FromNaiveStr {
+    fn accept_str(
+        &self,
+        _structure: &Structure,
+        buffers: &mut Buffers,
+        val: &str,
+    ) -> Result<usize> {
+        use chrono::{Datelike, NaiveDate};
+
+        let days = val.parse::<NaiveDate>()?.num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE;
+        buffers.u32[self.idx].push(days.to_bytes());
+        Ok(self.next)
+    }
+}
+
 impl Instruction for PushDate64FromNaiveStr {
     fn accept_str(
         &self,
@@ -703,20 +1011,69 @@ impl Instruction for PushDictionary {
             idx
         };
 
+        fn key<T: TryFrom<usize>>(idx: usize, ty: &str) -> Result<T> {
+            match T::try_from(idx) {
+                Ok(key) => Ok(key),
+                Err(_) => fail!(
+                    "Dictionary key overflow: the number of distinct values exceeds the range of the key type {ty}"
+                ),
+            }
+        }
+
         match self.indices {
-            I::U8(indices) => buffers.u8[indices].push(idx.try_into()?),
-            I::U16(indices) => buffers.u16[indices].push(idx.try_into()?),
-            I::U32(indices) => buffers.u32[indices].push(idx.try_into()?),
-            I::U64(indices) => buffers.u64[indices].push(idx.try_into()?),
-            I::I8(indices) => buffers.u8[indices].push(i8::try_from(idx)?.to_bytes()),
-            I::I16(indices) => buffers.u16[indices].push(u16::try_from(idx)?.to_bytes()),
-            I::I32(indices) => buffers.u32[indices].push(u32::try_from(idx)?.to_bytes()),
-            I::I64(indices) => buffers.u64[indices].push(u64::try_from(idx)?.to_bytes()),
+            I::U8(indices) => buffers.u8[indices].push(key(idx, "U8")?),
+            I::U16(indices) => buffers.u16[indices].push(key(idx, "U16")?),
+            I::U32(indices) => buffers.u32[indices].push(key(idx, "U32")?),
+            I::U64(indices) => buffers.u64[indices].push(key(idx, "U64")?),
+            I::I8(indices) => buffers.u8[indices].push(key::<i8>(idx, "I8")?.to_bytes()),
+            I::I16(indices) => buffers.u16[indices].push(key::<i16>(idx, "I16")?.to_bytes()),
+            I::I32(indices) => buffers.u32[indices].push(key::<i32>(idx, "I32")?.to_bytes()),
+            I::I64(indices) => buffers.u64[indices].push(key::<i64>(idx, "I64")?.to_bytes()),
         }
         Ok(self.next)
     }
 }
 
+impl Instruction for PushFormattedUtf8 {
+    format_primitive_handle!();
+
+    fn accept_str(&self, structure: &Structure, buffers: &mut Buffers, val: &str) -> Result<usize> {
+        let instr = PushUtf8 {
+            next: self.next,
+            buffer: self.buffer,
+            offsets: self.offsets,
+        };
+        instr.accept_str(structure, buffers, val)
+    }
+}
+
+impl Instruction for PushFormattedLargeUtf8 {
+    format_primitive_handle!();
+
+    fn accept_str(&self, structure: &Structure, buffers: &mut Buffers, val: &str) -> Result<usize> {
+        let instr = PushLargeUtf8 {
+            next: self.next,
+            buffer: self.buffer,
+            offsets: self.offsets,
+        };
+        instr.accept_str(structure, buffers, val)
+    }
+}
+
+impl Instruction for PushFormattedDictionary {
+    format_primitive_handle!();
+
+    fn accept_str(&self, structure: &Structure, buffers: &mut Buffers, val: &str) -> Result<usize> {
+        let instr = PushDictionary {
+            next: self.next,
+            values: self.values,
+            indices: self.indices,
+            dictionary: self.dictionary,
+        };
+        instr.accept_str(structure, buffers, val)
+    }
+}
+
 impl Instruction for UnionEnd {}
 
 impl Instruction for PushNull {
@@ -843,6 +1200,173 @@ impl_primitive_instruction!(
     },
 );
 
+impl PushCoercedNumber {
+    fn out_of_range<V: std::fmt::Display>(&self, val: V) -> Error {
+        error!(
+            "cannot coerce {val} into {data_type} for field {path}: value out of range",
+            data_type = self.data_type,
+            path = self.path,
+        )
+    }
+
+    fn narrow<T: TryFrom<i128>>(&self, val: i128) -> Result<T> {
+        T::try_from(val).map_err(|_| self.out_of_range(val))
+    }
+
+    fn push_int(&self, buffers: &mut Buffers, val: i128) -> Result<usize> {
+        use GenericDataType as D;
+
+        match self.data_type {
+            D::U8 => buffers.u8[self.idx].push(self.narrow::<u8>(val)?),
+            D::U16 => buffers.u16[self.idx].push(self.narrow::<u16>(val)?),
+            D::U32 => buffers.u32[self.idx].push(self.narrow::<u32>(val)?),
+            D::U64 => buffers.u64[self.idx].push(self.narrow::<u64>(val)?),
+            D::I8 => buffers.u8[self.idx].push(self.narrow::<i8>(val)?.to_bytes()),
+            D::I16 => buffers.u16[self.idx].push(self.narrow::<i16>(val)?.to_bytes()),
+            D::I32 => buffers.u32[self.idx].push(self.narrow::<i32>(val)?.to_bytes()),
+            D::I64 => buffers.u64[self.idx].push(self.narrow::<i64>(val)?.to_bytes()),
+            D::F16 | D::F32 | D::F64 => return self.push_float(buffers, val as f64),
+            dt => fail!("cannot coerce numbers into {dt}"),
+        }
+        Ok(self.next)
+    }
+
+    fn push_float(&self, buffers: &mut Buffers, val: f64) -> Result<usize> {
+        use GenericDataType as D;
+
+        match self.data_type {
+            D::F16 => {
+                if val.is_finite() && val.abs() > f16::MAX.to_f64() {
+                    return Err(self.out_of_range(val));
+                }
+                buffers.u16[self.idx].push(f16::from_f64(val).to_bits());
+            }
+            D::F32 => {
+                if val.is_finite() && val.abs() > f32::MAX as f64 {
+                    return Err(self.out_of_range(val));
+                }
+                buffers.u32[self.idx].push((val as f32).to_bytes());
+            }
+            D::F64 => buffers.u64[self.idx].push(val.to_bytes()),
+            dt => fail!(
+                "cannot coerce the float {val} into {dt} for field {path}",
+                path = self.path,
+            ),
+        }
+        Ok(self.next)
+    }
+
+    fn parse_str(&self, buffers: &mut Buffers, val: &str) -> Result<usize> {
+        use GenericDataType as D;
+
+        let parse_error = || {
+            error!(
+                "cannot parse {val:?} as {data_type} for field {path}",
+                data_type = self.data_type,
+                path = self.path,
+            )
+        };
+
+        let trimmed = val.trim();
+        match self.data_type {
+            D::F16 | D::F32 | D::F64 => {
+                let val = trimmed.parse::<f64>().map_err(|_| parse_error())?;
+                self.push_float(buffers, val)
+            }
+            _ => {
+                if let Ok(val) = trimmed.parse::<i128>() {
+                    return self.push_int(buffers, val);
+                }
+                // NOTE: accept integral values in float notation, e.g., "1e3"
+                let val = trimmed.parse::<f64>().map_err(|_| parse_error())?;
+                if !val.is_finite() || val.fract() != 0.0 {
+                    return Err(parse_error());
+                }
+                if val.abs() >= i128::MAX as f64 {
+                    return Err(self.out_of_range(val));
+                }
+                self.push_int(buffers, val as i128)
+            }
+        }
+    }
+}
+
+macro_rules! coerced_int_handle {
+    ($($name:ident: $ty:ty),*) => {
+        $(
+            fn $name(&self, _structure: &Structure, buffers: &mut Buffers, val: $ty) -> Result<usize> {
+                self.push_int(buffers, val.into())
+            }
+        )*
+    };
+}
+
+impl Instruction for PushCoercedNumber {
+    coerced_int_handle!(
+        accept_bool: bool,
+        accept_u8: u8,
+        accept_u16: u16,
+        accept_u32: u32,
+        accept_u64: u64,
+        accept_i8: i8,
+        accept_i16: i16,
+        accept_i32: i32,
+        accept_i64: i64
+    );
+
+    fn accept_f32(&self, _structure: &Structure, buffers: &mut Buffers, val: f32) -> Result<usize> {
+        self.push_float(buffers, val.into())
+    }
+
+    fn accept_f64(&self, _structure: &Structure, buffers: &mut Buffers, val: f64) -> Result<usize> {
+        self.push_float(buffers, val)
+    }
+
+    fn accept_str(
+        &self,
+        _structure: &Structure,
+        buffers: &mut Buffers,
+        val: &str,
+    ) -> Result<usize> {
+        if !self.from_str {
+            fail!(
+                "cannot accept the string {val:?} for the {data_type} field {path}",
+                data_type = self.data_type,
+                path = self.path,
+            );
+        }
+        self.parse_str(buffers, val)
+    }
+}
+
+impl Instruction for PushBoolFromStr {
+    fn accept_bool(
+        &self,
+        _structure: &Structure,
+        buffers: &mut Buffers,
+        val: bool,
+    ) -> Result<usize> {
+        buffers.u1[self.idx].push(val);
+        Ok(self.next)
+    }
+
+    fn accept_str(
+        &self,
+        _structure: &Structure,
+        buffers: &mut Buffers,
+        val: &str,
+    ) -> Result<usize> {
+        let Ok(parsed) = val.trim().parse::<bool>() else {
+            fail!(
+                "cannot parse {val:?} as Bool for field {path}",
+                path = self.path
+            );
+        };
+        buffers.u1[self.idx].push(parsed);
+        Ok(self.next)
+    }
+}
+
 impl Instruction for PushBool {
     fn accept_bool(
         &self,
@@ -856,8 +1380,11 @@ impl Instruction for PushBool {
 }
 
 macro_rules! dispatch_instruction {
-    ($this:expr, $method:ident) => {
+    ($this:expr, $event:expr, $method:ident) => {
         {
+            if $this.skipped.is_some() {
+                return $this.skip($event);
+            }
             $this.program_counter = dispatch_bytecode!(
                 &$this.structure.program[$this.program_counter],
                 instr => instr.$method(&$this.structure, &mut $this.buffers)?
@@ -865,8 +1392,11 @@ macro_rules! dispatch_instruction {
             Ok(())
         }
     };
-    ($this:expr, $method:ident, $($val:expr),*) => {
+    ($this:expr, $event:expr, $method:ident, $($val:expr),*) => {
         {
+            if $this.skipped.is_some() {
+                return $this.skip($event);
+            }
             $this.program_counter = dispatch_bytecode!(
                 &$this.structure.program[$this.program_counter],
                 instr => instr.$method(&$this.structure, &mut $this.buffers, $($val),*)?
@@ -912,105 +1442,141 @@ impl EventSink for Interpreter {
     }
 
     fn accept_bool(&mut self, val: bool) -> Result<()> {
-        dispatch_instruction!(self, accept_bool, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::Bool(val), accept_bool, val)
     }
 
     fn accept_u8(&mut self, val: u8) -> Result<()> {
-        dispatch_instruction!(self, accept_u8, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::U8(val), accept_u8, val)
     }
 
     fn accept_u16(&mut self, val: u16) -> Result<()> {
-        dispatch_instruction!(self, accept_u16, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::U16(val), accept_u16, val)
     }
 
     fn accept_u32(&mut self, val: u32) -> Result<()> {
-        dispatch_instruction!(self, accept_u32, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::U32(val), accept_u32, val)
     }
 
     fn accept_u64(&mut self, val: u64) -> Result<()> {
-        dispatch_instruction!(self, accept_u64, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::U64(val), accept_u64, val)
     }
 
     fn accept_i8(&mut self, val: i8) -> Result<()> {
-        dispatch_instruction!(self, accept_i8, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::I8(val), accept_i8, val)
     }
 
     fn accept_i16(&mut self, val: i16) -> Result<()> {
-        dispatch_instruction!(self, accept_i16, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::I16(val), accept_i16, val)
     }
 
     fn accept_i32(&mut self, val: i32) -> Result<()> {
-        dispatch_instruction!(self, accept_i32, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::I32(val), accept_i32, val)
     }
 
     fn accept_i64(&mut self, val: i64) -> Result<()> {
-        dispatch_instruction!(self, accept_i64, val)
+        if self.skip_unknown_formatted_field(&val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::I64(val), accept_i64, val)
     }
 
     fn accept_f32(&mut self, val: f32) -> Result<()> {
-        dispatch_instruction!(self, accept_f32, val)
+        dispatch_instruction!(self, Event::F32(val), accept_f32, val)
     }
 
     fn accept_f64(&mut self, val: f64) -> Result<()> {
-        dispatch_instruction!(self, accept_f64, val)
+        dispatch_instruction!(self, Event::F64(val), accept_f64, val)
     }
 
     fn accept_start_sequence(&mut self) -> crate::Result<()> {
-        dispatch_instruction!(self, accept_start_sequence)
+        dispatch_instruction!(self, Event::StartSequence, accept_start_sequence)
     }
 
     fn accept_end_sequence(&mut self) -> crate::Result<()> {
-        dispatch_instruction!(self, accept_end_sequence)
+        dispatch_instruction!(self, Event::EndSequence, accept_end_sequence)
     }
 
     fn accept_start_struct(&mut self) -> crate::Result<()> {
-        dispatch_instruction!(self, accept_start_struct)
+        dispatch_instruction!(self, Event::StartStruct, accept_start_struct)
     }
 
     fn accept_end_struct(&mut self) -> crate::Result<()> {
-        dispatch_instruction!(self, accept_end_struct)
+        dispatch_instruction!(self, Event::EndStruct, accept_end_struct)
     }
 
     fn accept_item(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_item)
+        dispatch_instruction!(self, Event::Item, accept_item)
     }
 
     fn accept_start_tuple(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_start_tuple)
+        dispatch_instruction!(self, Event::StartTuple, accept_start_tuple)
     }
 
     fn accept_end_tuple(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_end_tuple)
+        dispatch_instruction!(self, Event::EndTuple, accept_end_tuple)
     }
 
     fn accept_start_map(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_start_map)
+        dispatch_instruction!(self, Event::StartMap, accept_start_map)
     }
 
     fn accept_end_map(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_end_map)
+        dispatch_instruction!(self, Event::EndMap, accept_end_map)
     }
 
     fn accept_some(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_some)
+        dispatch_instruction!(self, Event::Some, accept_some)
     }
 
     fn accept_null(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_null)
+        dispatch_instruction!(self, Event::Null, accept_null)
     }
     fn accept_default(&mut self) -> Result<()> {
-        dispatch_instruction!(self, accept_default)
+        dispatch_instruction!(self, Event::Default, accept_default)
     }
 
     fn accept_str(&mut self, val: &str) -> Result<()> {
-        dispatch_instruction!(self, accept_str, val)
+        if self.skip_unknown_field(val, 1) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::Str(val), accept_str, val)
     }
 
     fn accept_variant(&mut self, name: &str, idx: usize) -> Result<()> {
-        dispatch_instruction!(self, accept_variant, name, idx)
+        if self.skip_unknown_field(name, 2) {
+            return Ok(());
+        }
+        dispatch_instruction!(self, Event::Variant(name, idx), accept_variant, name, idx)
     }
 
     fn finish(&mut self) -> Result<()> {
+        if self.skipped.is_some() {
+            fail!("finished interpreting while skipping the value of an unknown field");
+        }
         if !matches!(
             self.structure.program[self.program_counter],
             Bytecode::ProgramEnd(_)
@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 
 use serde::de::{
-    self, Deserialize, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+    self, Deserialize, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
 };
 
 use crate::internal::{
@@ -9,6 +10,27 @@ use crate::internal::{
     event::Event,
 };
 
+/// Configure how arrays are deserialized into Rust values
+///
+#[derive(Debug, Clone, Default)]
+pub struct DeserializationOptions {
+    /// If `true`, numbers and booleans are formatted as strings when the Rust
+    /// value expects a string
+    pub format_numbers_as_strings: bool,
+}
+
+impl DeserializationOptions {
+    /// Configure whether to format numbers and booleans as strings
+    ///
+    /// This allows to round-trip fields serialized with the `StrAsNumber` or
+    /// `StrAsBool` strategies into Rust strings.
+    ///
+    pub fn format_numbers_as_strings(mut self, value: bool) -> Self {
+        self.format_numbers_as_strings = value;
+        self
+    }
+}
+
 /// Deserialize a type from an [EventSource]
 ///
 /// This function may be helpful when creating custom formats.
@@ -20,9 +42,24 @@ pub fn deserialize_from_source<
     S: IntoEventSource<'event> + 'event,
 >(
     source: S,
+) -> Result<T> {
+    deserialize_from_source_with_options(source, DeserializationOptions::default())
+}
+
+/// Deserialize a type from an [EventSource] with the given options
+///
+pub fn deserialize_from_source_with_options<
+    'de,
+    'event,
+    T: Deserialize<'de>,
+    S: IntoEventSource<'event> + 'event,
+>(
+    source: S,
+    options: DeserializationOptions,
 ) -> Result<T> {
     let mut deserializer = Deserializer {
         source: PeekableEventSource::new(source.into_event_source()),
+        options,
     };
     let res = T::deserialize(&mut deserializer)?;
 
@@ -157,6 +194,29 @@ impl<'a, S: EventSource<'a>> IntoEventSource<'a> for S {
 
 pub struct Deserializer<'event, S: EventSource<'event>> {
     source: PeekableEventSource<'event, S>,
+    options: DeserializationOptions,
+}
+
+impl<'event, S: EventSource<'event>> Deserializer<'event, S> {
+    fn format_as_string(&self, ev: &Event<'_>) -> Option<String> {
+        if !self.options.format_numbers_as_strings {
+            return None;
+        }
+        match ev {
+            Event::Bool(val) => Some(val.to_string()),
+            Event::I8(val) => Some(val.to_string()),
+            Event::I16(val) => Some(val.to_string()),
+            Event::I32(val) => Some(val.to_string()),
+            Event::I64(val) => Some(val.to_string()),
+            Event::U8(val) => Some(val.to_string()),
+            Event::U16(val) => Some(val.to_string()),
+            Event::U32(val) => Some(val.to_string()),
+            Event::U64(val) => Some(val.to_string()),
+            Event::F32(val) => Some(val.to_string()),
+            Event::F64(val) => Some(val.to_string()),
+            _ => None,
+        }
+    }
 }
 
 impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
@@ -179,7 +239,12 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
             Some(Event::F64(_)) => self.deserialize_f64(visitor),
             Some(Event::Str(_)) => self.deserialize_str(visitor),
             Some(Event::OwnedStr(_)) => self.deserialize_string(visitor),
-            Some(Event::StartStruct) => self.deserialize_map(visitor),
+            Some(Event::Null) => self.deserialize_unit(visitor),
+            Some(Event::Some) => self.deserialize_option(visitor),
+            Some(Event::StartTuple) => self.deserialize_tuple(0, visitor),
+            // NOTE: null fields are only skipped in deserialize_map
+            Some(Event::StartStruct) => self.deserialize_struct("", &[], visitor),
+            Some(Event::StartMap) => self.deserialize_map(visitor),
             Some(Event::StartSequence) => self.deserialize_seq(visitor),
             Some(Event::Variant(_, _) | Event::OwnedVariant(_, _)) => {
                 self.deserialize_enum("", &[], visitor)
@@ -249,7 +314,10 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
         match required(self.source.next()?)? {
             Event::Str(val) => visitor.visit_str(val),
             Event::OwnedStr(val) => visitor.visit_str(&val),
-            ev => fail!("Invalid event {}, expected str", ev),
+            ev => match self.format_as_string(&ev) {
+                Some(val) => visitor.visit_str(&val),
+                None => fail!("Invalid event {}, expected str", ev),
+            },
         }
     }
 
@@ -257,7 +325,10 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
         match required(self.source.next()?)? {
             Event::Str(val) => visitor.visit_string(val.to_owned()),
             Event::OwnedStr(val) => visitor.visit_string(val),
-            ev => fail!("Invalid event {}, expected string", ev),
+            ev => match self.format_as_string(&ev) {
+                Some(val) => visitor.visit_string(val),
+                None => fail!("Invalid event {}, expected string", ev),
+            },
         }
     }
 
@@ -320,13 +391,16 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
     }
 
     fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
-        if !matches!(self.source.next()?, Some(Event::StartTuple)) {
-            fail!("Expected start of tuple");
-        }
+        // tuples serialized with `Strategy::TupleAsList` are emitted as sequences
+        let end = match self.source.next()? {
+            Some(Event::StartTuple) => Event::EndTuple,
+            Some(Event::StartSequence) => Event::EndSequence,
+            _ => fail!("Expected start of tuple"),
+        };
 
         let res = visitor.visit_seq(&mut *self)?;
 
-        if !matches!(self.source.next()?, Some(Event::EndTuple)) {
+        if self.source.next()?.as_ref() != Some(&end) {
             fail!("Expected end of tuple");
         }
         Ok(res)
@@ -335,29 +409,19 @@ impl<'de, 'a, 'event, S: EventSource<'event>> de::Deserializer<'de>
     fn deserialize_tuple_struct<V: Visitor<'de>>(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
         visitor: V,
     ) -> Result<V::Value> {
-        if !matches!(self.source.next()?, Some(Event::StartTuple)) {
-            fail!("Expected start of tuple");
-        }
-
-        let res = visitor.visit_seq(&mut *self)?;
-
-        if !matches!(self.source.next()?, Some(Event::EndTuple)) {
-            fail!("Expected end of tuple");
-        }
-        Ok(res)
+        self.deserialize_tuple(len, visitor)
     }
 
     fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        match self.source.next()? {
-            Some(Event::StartMap) | Some(Event::StartStruct) => {}
+        let res = match self.source.next()? {
+            Some(Event::StartMap) => visitor.visit_map(&mut *self)?,
+            Some(Event::StartStruct) => visitor.visit_map(StructAsMapAccess(&mut *self))?,
             Some(ev) => fail!("Expected StartMap, got Some({ev})"),
             None => fail!("Expected StartMap, got None"),
-        }
-
-        let res = visitor.visit_map(&mut *self)?;
+        };
 
         match self.source.next()? {
             Some(Event::EndMap) | Some(Event::EndStruct) => {}
@@ -435,7 +499,18 @@ impl<'de, 'a, 'event, S: EventSource<'event>> MapAccess<'de> for &'a mut Deseria
         ) {
             return Ok(None);
         }
-        seed.deserialize(&mut **self).map(Some)
+        // string keys may encode other types, e.g., for maps serialized as structs
+        match self.source.peek()? {
+            Some(Event::Str(_) | Event::OwnedStr(_)) => {
+                let key = match required(self.source.next()?)? {
+                    Event::Str(key) => Cow::Borrowed(key),
+                    Event::OwnedStr(key) => Cow::Owned(key),
+                    _ => unreachable!(),
+                };
+                seed.deserialize(MapKeyDeserializer(key.as_ref())).map(Some)
+            }
+            _ => seed.deserialize(&mut **self).map(Some),
+        }
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
@@ -446,6 +521,114 @@ impl<'de, 'a, 'event, S: EventSource<'event>> MapAccess<'de> for &'a mut Deseria
     }
 }
 
+/// Access the fields of a struct as the entries of a map
+///
+/// Fields with null values are skipped, i.e., treated as missing keys. It is
+/// only used by `deserialize_map`, `deserialize_any` keeps null fields.
+///
+struct StructAsMapAccess<'a, 'event, S: EventSource<'event>>(&'a mut Deserializer<'event, S>);
+
+impl<'de, 'a, 'event, S: EventSource<'event>> MapAccess<'de> for StructAsMapAccess<'a, 'event, S> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        loop {
+            if matches!(self.0.source.peek()?, Some(Event::EndStruct)) {
+                return Ok(None);
+            }
+
+            let key = match required(self.0.source.next()?)? {
+                Event::Str(key) => Cow::Borrowed(key),
+                Event::OwnedStr(key) => Cow::Owned(key),
+                ev => fail!("Invalid event {ev}, expected a struct field name"),
+            };
+
+            if matches!(self.0.source.peek()?, Some(Event::Null)) {
+                self.0.source.next()?;
+                continue;
+            }
+
+            return seed.deserialize(MapKeyDeserializer(key.as_ref())).map(Some);
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.0)
+    }
+}
+
+/// Deserialize map keys that were formatted as strings
+struct MapKeyDeserializer<'a>(&'a str);
+
+macro_rules! parse_map_key {
+    ($($name:ident => $visit:ident),*) => {
+        $(
+            fn $name<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                match self.0.parse() {
+                    Ok(val) => visitor.$visit(val),
+                    Err(_) => fail!(
+                        "Cannot parse map key {key:?} in {name}",
+                        key = self.0,
+                        name = stringify!($name),
+                    ),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for MapKeyDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_str(self.0)
+    }
+
+    parse_map_key!(
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64
+    );
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    serde::forward_to_deserialize_any! {
+        f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
 impl<'de, 'a, 'event, S: EventSource<'event>> EnumAccess<'de> for &'a mut Deserializer<'event, S> {
     type Error = Error;
     type Variant = Self;
@@ -0,0 +1,474 @@
+//! Trace the schema of a type from its `Deserialize` implementation
+//!
+//! The type is deserialized from a deserializer that produces sample values
+//! and records the requested types as events for the schema tracer. Enums are
+//! traced over multiple passes, until all variants (including variants of
+//! nested enums) have been seen.
+//!
+use std::collections::HashMap;
+
+use serde::de::{
+    self, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess,
+    Visitor,
+};
+
+use crate::internal::{
+    error::{error, fail, Error, Result},
+    event::Event,
+    schema::Tracer,
+    sink::EventSink,
+};
+
+/// Trace the given type as a single record
+///
+/// The type is deserialized repeatedly until all enum variants were seen. Each
+/// pass is traced as an individual record.
+///
+pub fn trace_type<'de, T: de::Deserialize<'de>>(tracer: &mut Tracer) -> Result<()> {
+    let mut state = TypeTracingState::default();
+
+    loop {
+        state.incomplete = false;
+
+        let deserializer = TypeDeserializer {
+            tracer: &mut *tracer,
+            state: &mut state,
+            path: String::from("$"),
+        };
+        T::deserialize(deserializer)?;
+
+        if !state.incomplete {
+            break;
+        }
+    }
+
+    tracer.finish()
+}
+
+#[derive(Debug, Default)]
+struct TypeTracingState {
+    /// The next variant to trace for the enum at the given path
+    variants: HashMap<String, usize>,
+    /// Whether the current pass left unexplored variants
+    incomplete: bool,
+    /// The visitor types of the structs and enums currently being traced to
+    /// detect recursive types
+    ///
+    /// The serde names are not unique, e.g., for types of the same name
+    /// defined in different modules, therefore the type names of the visitors
+    /// are used.
+    stack: Vec<&'static str>,
+}
+
+struct TypeDeserializer<'a> {
+    tracer: &'a mut Tracer,
+    state: &'a mut TypeTracingState,
+    path: String,
+}
+
+impl<'a> TypeDeserializer<'a> {
+    fn child(&mut self, path: String) -> TypeDeserializer<'_> {
+        TypeDeserializer {
+            tracer: &mut *self.tracer,
+            state: &mut *self.state,
+            path,
+        }
+    }
+
+    fn enter<V>(&mut self, name: &'static str) -> Result<()> {
+        let visitor = std::any::type_name::<V>();
+        if self.state.stack.contains(&visitor) {
+            fail!(
+                "Cannot trace the recursive type {name} ({path})",
+                path = self.path
+            );
+        }
+        self.state.stack.push(visitor);
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.state.stack.pop();
+    }
+}
+
+macro_rules! trace_primitive {
+    ($($name:ident => $visit:ident($val:expr), $event:ident;)*) => {
+        $(
+            fn $name<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+                self.tracer.accept(Event::$event($val))?;
+                visitor.$visit($val)
+            }
+        )*
+    };
+}
+
+impl<'de, 'a> de::Deserializer<'de> for TypeDeserializer<'a> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value> {
+        fail!(
+            concat!(
+                "Type tracing does not support self describing types ({path}), e.g., ",
+                "untagged or internally tagged enums, flattened structs, or serde_json::Value",
+            ),
+            path = self.path,
+        );
+    }
+
+    trace_primitive!(
+        deserialize_bool => visit_bool(false), Bool;
+        deserialize_i8 => visit_i8(0), I8;
+        deserialize_i16 => visit_i16(0), I16;
+        deserialize_i32 => visit_i32(0), I32;
+        deserialize_i64 => visit_i64(0), I64;
+        deserialize_u8 => visit_u8(0), U8;
+        deserialize_u16 => visit_u16(0), U16;
+        deserialize_u32 => visit_u32(0), U32;
+        deserialize_u64 => visit_u64(0), U64;
+        deserialize_f32 => visit_f32(0.0), F32;
+        deserialize_f64 => visit_f64(0.0), F64;
+    );
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let sample = sample_str::<V>();
+        self.tracer.accept(Event::Str(sample))?;
+        visitor.visit_borrowed_str(sample).map_err(|err: Error| {
+            error!(
+                concat!(
+                    "Cannot trace the type parsed by {visitor} from a sample string ({path}): ",
+                    "{err}. Consider tracing the schema from samples instead.",
+                ),
+                visitor = std::any::type_name::<V>(),
+                path = self.path,
+                err = err,
+            )
+        })
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.tracer.accept(Event::U32(0))?;
+        visitor.visit_char('\0')
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.tracer.accept(Event::StartSequence)?;
+        self.tracer.accept(Event::Item)?;
+        self.tracer.accept(Event::U8(0))?;
+        self.tracer.accept(Event::EndSequence)?;
+        visitor.visit_borrowed_bytes(&[])
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.tracer.accept(Event::Some)?;
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.tracer.accept(Event::Null)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value> {
+        self.tracer.accept(Event::StartSequence)?;
+        let path = format!("{path}.element", path = self.path);
+        let res = visitor.visit_seq(SeqTraceAccess {
+            deserializer: self.child(String::new()),
+            paths: vec![path],
+            next: 0,
+        })?;
+        self.tracer.accept(Event::EndSequence)?;
+        Ok(res)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(mut self, len: usize, visitor: V) -> Result<V::Value> {
+        self.tracer.accept(Event::StartTuple)?;
+        let paths = (0..len)
+            .map(|idx| format!("{path}.{idx}", path = self.path))
+            .collect();
+        let res = visitor.visit_seq(SeqTraceAccess {
+            deserializer: self.child(String::new()),
+            paths,
+            next: 0,
+        })?;
+        self.tracer.accept(Event::EndTuple)?;
+        Ok(res)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(mut self, visitor: V) -> Result<V::Value> {
+        self.tracer.accept(Event::StartMap)?;
+        let path = self.path.clone();
+        let res = visitor.visit_map(MapTraceAccess {
+            deserializer: self.child(path),
+            done: false,
+        })?;
+        self.tracer.accept(Event::EndMap)?;
+        Ok(res)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        mut self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        self.enter::<V>(name)?;
+        self.tracer.accept(Event::StartStruct)?;
+        let path = self.path.clone();
+        let res = visitor.visit_map(StructTraceAccess {
+            deserializer: self.child(path),
+            fields,
+            next: 0,
+        })?;
+        self.tracer.accept(Event::EndStruct)?;
+        self.leave();
+        Ok(res)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        mut self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        if variants.is_empty() {
+            fail!(
+                "Cannot trace the enum {name} without variants ({path})",
+                path = self.path
+            );
+        }
+        self.enter::<V>(name)?;
+
+        let variant = self.state.variants.get(&self.path).copied().unwrap_or(0);
+        let outer_incomplete = std::mem::replace(&mut self.state.incomplete, false);
+
+        self.tracer
+            .accept(Event::Variant(variants[variant], variant))?;
+        let path = format!("{path}.{name}", path = self.path, name = variants[variant]);
+        let res = visitor.visit_enum(EnumTraceAccess {
+            deserializer: self.child(path),
+            variant,
+        })?;
+
+        // NOTE: only advance to the next variant, once all nested variants were seen
+        let incomplete = if self.state.incomplete {
+            true
+        } else if variant + 1 < variants.len() {
+            self.state.variants.insert(self.path.clone(), variant + 1);
+            true
+        } else {
+            false
+        };
+        self.state.incomplete = outer_incomplete || incomplete;
+
+        self.leave();
+        Ok(res)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+}
+
+/// Sample strings for visitors that parse strings, e.g., of chrono types
+///
+/// The samples are chosen such that they are detected by
+/// [`TracingOptions::guess_dates`][crate::schema::TracingOptions::guess_dates].
+///
+const SAMPLE_STRS: &[(&str, &str)] = &[
+    ("::NaiveDateVisitor", "1970-01-01"),
+    ("::NaiveTimeVisitor", "00:00:00"),
+    ("::NaiveDateTimeVisitor", "1970-01-01T00:00:00"),
+    ("::DateTimeVisitor", "1970-01-01T00:00:00Z"),
+];
+
+fn sample_str<V>() -> &'static str {
+    let visitor = std::any::type_name::<V>();
+    if !visitor.starts_with("chrono::") {
+        return "";
+    }
+    for (suffix, sample) in SAMPLE_STRS {
+        if visitor.ends_with(suffix) {
+            return sample;
+        }
+    }
+    ""
+}
+
+struct SeqTraceAccess<'a> {
+    deserializer: TypeDeserializer<'a>,
+    paths: Vec<String>,
+    next: usize,
+}
+
+impl<'de, 'a> SeqAccess<'de> for SeqTraceAccess<'a> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
+        let Some(path) = self.paths.get(self.next) else {
+            return Ok(None);
+        };
+        let path = path.clone();
+        self.next += 1;
+
+        self.deserializer.tracer.accept(Event::Item)?;
+        let res = seed.deserialize(self.deserializer.child(path))?;
+        Ok(Some(res))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.paths.len() - self.next)
+    }
+}
+
+struct MapTraceAccess<'a> {
+    deserializer: TypeDeserializer<'a>,
+    done: bool,
+}
+
+impl<'de, 'a> MapAccess<'de> for MapTraceAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        self.deserializer.tracer.accept(Event::Item)?;
+        let path = format!("{path}.key", path = self.deserializer.path);
+        let res = seed.deserialize(self.deserializer.child(path))?;
+        Ok(Some(res))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        self.done = true;
+
+        let path = format!("{path}.value", path = self.deserializer.path);
+        seed.deserialize(self.deserializer.child(path))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(if self.done { 0 } else { 1 })
+    }
+}
+
+struct StructTraceAccess<'a> {
+    deserializer: TypeDeserializer<'a>,
+    fields: &'static [&'static str],
+    next: usize,
+}
+
+impl<'de, 'a> MapAccess<'de> for StructTraceAccess<'a> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        let Some(field) = self.fields.get(self.next) else {
+            return Ok(None);
+        };
+
+        self.deserializer.tracer.accept(Event::Str(field))?;
+        let res = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(*field))?;
+        Ok(Some(res))
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        let Some(field) = self.fields.get(self.next) else {
+            fail!("Invalid call to next_value_seed without a key");
+        };
+        self.next += 1;
+
+        let path = format!("{path}.{field}", path = self.deserializer.path);
+        seed.deserialize(self.deserializer.child(path))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len() - self.next)
+    }
+}
+
+struct EnumTraceAccess<'a> {
+    deserializer: TypeDeserializer<'a>,
+    variant: usize,
+}
+
+impl<'de, 'a> EnumAccess<'de> for EnumTraceAccess<'a> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant)> {
+        let variant = u32::try_from(self.variant)?;
+        let res = seed.deserialize(IntoDeserializer::<Error>::into_deserializer(variant))?;
+        Ok((res, self))
+    }
+}
+
+impl<'de, 'a> VariantAccess<'de> for EnumTraceAccess<'a> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        self.deserializer.tracer.accept(Event::Null)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value> {
+        seed.deserialize(self.deserializer)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        de::Deserializer::deserialize_tuple(self.deserializer, len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        // NOTE: struct variants are not named, do not check for recursion
+        let mut deserializer = self.deserializer;
+        deserializer.tracer.accept(Event::StartStruct)?;
+        let path = deserializer.path.clone();
+        let res = visitor.visit_map(StructTraceAccess {
+            deserializer: deserializer.child(path),
+            fields,
+            next: 0,
+        })?;
+        deserializer.tracer.accept(Event::EndStruct)?;
+        Ok(res)
+    }
+}
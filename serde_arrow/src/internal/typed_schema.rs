@@ -0,0 +1,149 @@
+//! Schemas known at compile time
+//!
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use crate::internal::schema::{GenericDataType, GenericField, SerdeArrowSchema, Strategy};
+
+/// A type with a fixed Arrow field
+///
+/// The fields follow the defaults of tracing, e.g., `LargeUtf8` for strings and
+/// `LargeList` for vectors. Maps are described as Arrow maps and chrono's date
+/// times as `Date64` / `Date32` fields with the corresponding strategies.
+/// Implementations for structs are generated by `#[derive(ArrowSchema)]`
+/// (requires the `derive` feature).
+///
+pub trait ArrowField {
+    /// Build the field describing values of this type
+    fn arrow_field(name: &str) -> GenericField;
+}
+
+/// A record type with a fixed Arrow schema
+///
+/// Implementations are generated by `#[derive(ArrowSchema)]` (requires the
+/// `derive` feature). For example:
+///
+/// ```rust
+/// # #[cfg(all(feature = "derive", feature = "arrow2-0-17"))]
+/// # fn main() {
+/// # use serde_arrow::_impl::arrow2::datatypes::{DataType, Field};
+/// use serde::Serialize;
+/// use serde_arrow::{arrow2::serialize_into_arrays, schema::ArrowSchema};
+///
+/// ##[derive(Serialize, ArrowSchema)]
+/// struct Record {
+///     a: Option<f32>,
+///     #[arrow(dictionary)]
+///     b: String,
+/// }
+///
+/// let fields = Record::arrow_schema().to_arrow2_fields().unwrap();
+/// let arrays = serialize_into_arrays(
+///     &fields,
+///     &[Record { a: Some(1.0), b: String::from("x") }],
+/// ).unwrap();
+/// # }
+/// # #[cfg(not(all(feature = "derive", feature = "arrow2-0-17")))]
+/// # fn main() {}
+/// ```
+///
+pub trait ArrowSchema {
+    /// Build the schema of records of this type
+    fn arrow_schema() -> SerdeArrowSchema;
+}
+
+macro_rules! impl_primitive_arrow_field {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl ArrowField for $ty {
+                fn arrow_field(name: &str) -> GenericField {
+                    GenericField::new(name, GenericDataType::$variant, false)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_arrow_field!(
+    bool => Bool,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    half::f16 => F16,
+    f32 => F32,
+    f64 => F64,
+    String => LargeUtf8,
+);
+
+impl ArrowField for () {
+    fn arrow_field(name: &str) -> GenericField {
+        GenericField::new(name, GenericDataType::Null, true)
+    }
+}
+
+impl ArrowField for DateTime<Utc> {
+    fn arrow_field(name: &str) -> GenericField {
+        GenericField::new(name, GenericDataType::Date64, false)
+            .with_strategy(Strategy::UtcStrAsDate64)
+    }
+}
+
+impl ArrowField for NaiveDateTime {
+    fn arrow_field(name: &str) -> GenericField {
+        GenericField::new(name, GenericDataType::Date64, false)
+            .with_strategy(Strategy::NaiveStrAsDate64)
+    }
+}
+
+impl ArrowField for NaiveDate {
+    fn arrow_field(name: &str) -> GenericField {
+        GenericField::new(name, GenericDataType::Date32, false)
+            .with_strategy(Strategy::NaiveStrAsDate32)
+    }
+}
+
+impl<T: ArrowField> ArrowField for Option<T> {
+    fn arrow_field(name: &str) -> GenericField {
+        let mut field = T::arrow_field(name);
+        field.nullable = true;
+        field
+    }
+}
+
+impl<T: ArrowField + ?Sized> ArrowField for Box<T> {
+    fn arrow_field(name: &str) -> GenericField {
+        T::arrow_field(name)
+    }
+}
+
+impl<T: ArrowField> ArrowField for Vec<T> {
+    fn arrow_field(name: &str) -> GenericField {
+        GenericField::new(name, GenericDataType::LargeList, false)
+            .with_child(T::arrow_field("element"))
+    }
+}
+
+fn map_field<K: ArrowField, V: ArrowField>(name: &str) -> GenericField {
+    let entries = GenericField::new("entries", GenericDataType::Struct, false)
+        .with_child(K::arrow_field("key"))
+        .with_child(V::arrow_field("value"));
+    GenericField::new(name, GenericDataType::Map, false).with_child(entries)
+}
+
+impl<K: ArrowField, V: ArrowField, S> ArrowField for HashMap<K, V, S> {
+    fn arrow_field(name: &str) -> GenericField {
+        map_field::<K, V>(name)
+    }
+}
+
+impl<K: ArrowField, V: ArrowField> ArrowField for BTreeMap<K, V> {
+    fn arrow_field(name: &str) -> GenericField {
+        map_field::<K, V>(name)
+    }
+}
@@ -123,6 +123,11 @@
 //! | `arrow2-0-17` | `arrow2=0.17` |
 //! | `arrow2-0-16` | `arrow2=0.16` |
 //!
+//! The `json` feature implements `Serialize` and `Deserialize` for
+//! `SerdeArrowSchema` and enables the JSON helpers, e.g.,
+//! `SerdeArrowSchema::to_json` and `SerdeArrowSchema::to_json_schema`. It
+//! adds dependencies on `serde_json` and the `derive` feature of `serde`.
+//!
 mod internal;
 
 /// Internal. Do not use
@@ -318,12 +323,29 @@ pub mod base {
 /// When tracing the schema using the `serialize_into_fields` methods, the
 /// following defaults are used:
 ///
-/// - Strings: `LargeUtf8`, i.e., i64 offsets
-/// - Lists: `LargeList`, i.e., i64 offsets
-/// - Strings with dictionary encoding: U32 keys and LargeUtf8 values
+/// - Strings: `LargeUtf8`, i.e., i64 offsets. Configurable with
+///   [`TracingOptions::string_type`][crate::internal::schema::TracingOptions::string_type]
+/// - Lists: `LargeList`, i.e., i64 offsets. Configurable with
+///   [`TracingOptions::list_type`][crate::internal::schema::TracingOptions::list_type]
+/// - Strings with dictionary encoding: U32 keys and LargeUtf8 values. The key
+///   type is configurable with
+///   [`TracingOptions::dictionary_key_type`][crate::internal::schema::TracingOptions::dictionary_key_type]
 ///   - Rationale: `polars` cannot handle 64 bit keys in its default
 ///     configuration
 ///
+/// [`SerdeArrowSchema`][crate::internal::schema::SerdeArrowSchema] describes a
+/// schema independent of the arrow implementation. It can be traced directly
+/// with `SerdeArrowSchema::from_samples` or `SerdeArrowSchema::from_type` and
+/// converted to and from the fields of `arrow` and `arrow2`. With the `json`
+/// feature, it implements `Serialize` and `Deserialize` to store schemas, e.g.,
+/// as JSON via `SerdeArrowSchema::to_json` and `SerdeArrowSchema::from_json`.
+/// `SerdeArrowSchema::to_rust_code` generates Rust types matching the schema.
+/// `SerdeArrowSchema::to_json_schema` and `SerdeArrowSchema::from_json_schema`
+/// convert schemas to and from JSON Schema documents (requires the `json`
+/// feature).
+/// [`diff_schemas`][crate::internal::diff::diff_schemas] reports the
+/// differences between two schemas, e.g., to detect schema drift.
+///
 /// Null-only fields (e.g., fields of type `()` or fields with only `None`
 /// entries) result in errors per default.
 /// [`TracingOptions::allow_null_fields`][crate::internal::schema::TracingOptions::allow_null_fields]
@@ -351,12 +373,26 @@ pub mod base {
 ///
 /// [find_field_mut]: crate::arrow2::experimental::find_field_mut
 ///
+/// For arrow, the experimental functions in `serde_arrow::arrow::experimental`
+/// (e.g., `set_field_type`, `insert_field`, `remove_field` and `rename_field`)
+/// rebuild nested schemas along a dotted path.
+///
 pub mod schema {
-    pub use crate::internal::schema::{Strategy, TracingOptions, STRATEGY_KEY};
+    pub use crate::internal::diff::{diff_schemas, SchemaDiff, SchemaDifference};
+    pub use crate::internal::schema::{
+        DictionaryKeyType, GenericDataType, GenericField, ListType, SerdeArrowSchema, Strategy,
+        StringType, TracingOptions, STRATEGY_KEY,
+    };
+    pub use crate::internal::typed_schema::{ArrowField, ArrowSchema};
+
+    #[cfg(feature = "derive")]
+    pub use serde_arrow_derive::ArrowSchema;
 }
 
 /// Experimental functionality that is not bound by semver compatibility
 ///
 pub mod experimental {
+    pub use crate::internal::serialization::CompilationOptions;
+    pub use crate::internal::source::DeserializationOptions;
     pub use crate::internal::{configure, Configuration};
 }
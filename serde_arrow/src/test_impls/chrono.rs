@@ -77,3 +77,104 @@ test_example!(
         struct T(#[serde(with = "chrono::serde::ts_milliseconds")] DateTime<Utc>);
     },
 );
+
+test_example!(
+    test_name = naive_date_as_str,
+    field = GenericField::new("root", GenericDataType::LargeUtf8, false),
+    ty = NaiveDate,
+    values = [
+        NaiveDate::from_ymd_opt(2020, 12, 24).unwrap(),
+        NaiveDate::from_ymd_opt(1960, 5, 5).unwrap(),
+    ],
+    nulls = [false, false],
+    define = {
+        use chrono::NaiveDate;
+    },
+);
+
+test_example!(
+    test_name = naive_date_as_date32,
+    field = GenericField::new("root", GenericDataType::LargeUtf8, false),
+    overwrite_field = GenericField::new("root", GenericDataType::Date32, false)
+        .with_strategy(Strategy::NaiveStrAsDate32),
+    ty = NaiveDate,
+    values = [
+        NaiveDate::from_ymd_opt(2020, 12, 24).unwrap(),
+        NaiveDate::from_ymd_opt(1960, 5, 5).unwrap(),
+    ],
+    nulls = [false, false],
+    define = {
+        use chrono::NaiveDate;
+    },
+);
+
+test_example!(
+    test_name = guess_dates_utc,
+    tracing_options = TracingOptions::default().guess_dates(true),
+    field = GenericField::new("root", GenericDataType::Date64, false)
+        .with_strategy(Strategy::UtcStrAsDate64),
+    ty = DateTime<Utc>,
+    values = [
+        Utc.with_ymd_and_hms(2020, 12, 24, 8, 30, 0).unwrap(),
+        Utc.with_ymd_and_hms(2023, 5, 5, 16, 6, 0).unwrap()
+    ],
+    nulls = [false, false],
+    define = {
+        use chrono::{DateTime, Utc, TimeZone};
+    },
+);
+
+test_example!(
+    test_name = guess_dates_naive,
+    tracing_options = TracingOptions::default().guess_dates(true),
+    field = GenericField::new("root", GenericDataType::Date64, true)
+        .with_strategy(Strategy::NaiveStrAsDate64),
+    ty = Option<NaiveDateTime>,
+    values = [
+        Some(NaiveDateTime::from_timestamp_millis(1662921288000).unwrap()),
+        None,
+        Some(NaiveDateTime::from_timestamp_millis(-2208936075000).unwrap()),
+    ],
+    nulls = [false, true, false],
+    define = {
+        use chrono::NaiveDateTime;
+    },
+);
+
+test_example!(
+    test_name = guess_dates_naive_date,
+    tracing_options = TracingOptions::default().guess_dates(true),
+    field = GenericField::new("root", GenericDataType::Date32, false)
+        .with_strategy(Strategy::NaiveStrAsDate32),
+    ty = NaiveDate,
+    values = [
+        NaiveDate::from_ymd_opt(2020, 12, 24).unwrap(),
+        NaiveDate::from_ymd_opt(1960, 5, 5).unwrap(),
+    ],
+    nulls = [false, false],
+    define = {
+        use chrono::NaiveDate;
+    },
+);
+
+test_example!(
+    test_name = guess_dates_mixed_formats,
+    tracing_options = TracingOptions::default().guess_dates(true),
+    field = GenericField::new("root", GenericDataType::LargeUtf8, false),
+    ty = String,
+    values = [
+        String::from("2023-01-31"),
+        String::from("2023-01-31T12:00:00"),
+        String::from("2023-01-31T12:00:00Z"),
+    ],
+    nulls = [false, false, false],
+);
+
+test_example!(
+    test_name = guess_dates_non_dates,
+    tracing_options = TracingOptions::default().guess_dates(true),
+    field = GenericField::new("root", GenericDataType::LargeUtf8, false),
+    ty = String,
+    values = [String::from("2023-01-31"), String::from("foo")],
+    nulls = [false, false],
+);
@@ -1,4 +1,4 @@
-use super::macros::test_example;
+use super::macros::{test_error, test_example};
 
 test_example!(
     test_name = string_dict_u32,
@@ -565,3 +565,122 @@ test_example!(
     values = [Some(String::from("a")), None, Some(String::from("a"))],
     nulls = [false, true, false],
 );
+
+test_example!(
+    test_name = auto_dictionary_low_cardinality,
+    test_deserialization = [],
+    tracing_options = TracingOptions::default().auto_dictionary_encoding(true),
+    field = GenericField::new("root", GenericDataType::Dictionary, false)
+        .with_child(GenericField::new("key", GenericDataType::U32, false))
+        .with_child(GenericField::new(
+            "value",
+            GenericDataType::LargeUtf8,
+            false
+        )),
+    ty = String,
+    values = [
+        String::from("a"),
+        String::from("b"),
+        String::from("a"),
+        String::from("b"),
+        String::from("a"),
+    ],
+    nulls = [false, false, false, false, false],
+);
+
+test_example!(
+    test_name = auto_dictionary_high_ratio,
+    tracing_options = TracingOptions::default().auto_dictionary_encoding(true),
+    field = GenericField::new("root", GenericDataType::LargeUtf8, false),
+    ty = String,
+    values = [String::from("a"), String::from("b"), String::from("c")],
+    nulls = [false, false, false],
+);
+
+test_example!(
+    test_name = auto_dictionary_too_many_distinct_values,
+    tracing_options = TracingOptions::default()
+        .auto_dictionary_encoding(true)
+        .dictionary_max_distinct(1)
+        .dictionary_max_ratio(1.0),
+    field = GenericField::new("root", GenericDataType::LargeUtf8, true),
+    ty = Option<String>,
+    values = [Some(String::from("a")), None, Some(String::from("b")), Some(String::from("a"))],
+    nulls = [false, true, false, false],
+);
+
+test_example!(
+    test_name = auto_dictionary_custom_ratio,
+    test_deserialization = [],
+    tracing_options = TracingOptions::default()
+        .auto_dictionary_encoding(true)
+        .dictionary_max_ratio(1.0),
+    field = GenericField::new("root", GenericDataType::Dictionary, true)
+        .with_child(GenericField::new("key", GenericDataType::U32, false))
+        .with_child(GenericField::new("value", GenericDataType::LargeUtf8, false)),
+    ty = Option<String>,
+    values = [Some(String::from("a")), None, Some(String::from("b"))],
+    nulls = [false, true, false],
+);
+
+test_example!(
+    test_name = dictionary_key_type_i16,
+    test_deserialization = [],
+    tracing_options = TracingOptions::default()
+        .string_dictionary_encoding(true)
+        .dictionary_key_type(DictionaryKeyType::I16),
+    field = GenericField::new("root", GenericDataType::Dictionary, false)
+        .with_child(GenericField::new("key", GenericDataType::I16, false))
+        .with_child(GenericField::new(
+            "value",
+            GenericDataType::LargeUtf8,
+            false
+        )),
+    ty = String,
+    values = [String::from("a"), String::from("b"), String::from("a")],
+    nulls = [false, false, false],
+    define = {
+        use crate::schema::DictionaryKeyType;
+    },
+);
+
+test_example!(
+    test_name = dictionary_key_type_auto,
+    test_deserialization = [],
+    tracing_options = TracingOptions::default()
+        .string_dictionary_encoding(true)
+        .dictionary_key_type(DictionaryKeyType::Auto),
+    field = GenericField::new("root", GenericDataType::Dictionary, false)
+        .with_child(GenericField::new("key", GenericDataType::U8, false))
+        .with_child(GenericField::new(
+            "value",
+            GenericDataType::LargeUtf8,
+            false
+        )),
+    ty = String,
+    values = [String::from("a"), String::from("b"), String::from("a")],
+    nulls = [false, false, false],
+    define = {
+        use crate::schema::DictionaryKeyType;
+    },
+);
+
+test_error!(
+    test_name = dictionary_key_overflow,
+    expected_error = "Dictionary key overflow",
+    block = {
+        use crate::schema::{DictionaryKeyType, TracingOptions};
+
+        let tracing_options = TracingOptions::default()
+            .string_dictionary_encoding(true)
+            .dictionary_key_type(DictionaryKeyType::Auto);
+
+        let items = (0..10).map(|i| i.to_string()).collect::<Vec<_>>();
+        let field = serialize_into_field(&items, "root", tracing_options).unwrap();
+
+        let items = (0..1000).map(|i| i.to_string()).collect::<Vec<_>>();
+        serialize_into_array(&field, &items)?;
+
+        Ok(())
+    },
+);
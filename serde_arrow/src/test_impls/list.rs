@@ -47,3 +47,18 @@ test_example!(
     values = [vec![vec![0, 1, 2], vec![3, 4]], vec![vec![5, 6], vec![]], vec![]],
     nulls = [false, false, false],
 );
+
+test_example!(
+    test_name = list_type_list,
+    tracing_options = TracingOptions::default().list_type(ListType::List),
+    field = GenericField::new("root", GenericDataType::List, false).with_child(
+        GenericField::new("element", GenericDataType::List, false)
+            .with_child(GenericField::new("element", GenericDataType::U32, false))
+    ),
+    ty = Vec<Vec<u32>>,
+    values = [vec![vec![0, 1], vec![2]], vec![], vec![vec![]]],
+    nulls = [false, false, false],
+    define = {
+        use crate::schema::ListType;
+    },
+);
@@ -257,6 +257,8 @@ macro_rules! test_events {
         events = $events:expr,
     ) => {
         mod $test_name {
+            use std::sync::Arc;
+
             use crate::internal::{
                 serialization::{compile_serialization, CompilationOptions, Interpreter},
                 event::Event,
@@ -273,7 +275,7 @@ macro_rules! test_events {
                 let options = TracingOptions::default();
                 $(let options = $tracing_options;)?
 
-                let tracer = Tracer::new(String::from("$"), options);
+                let tracer = Tracer::new(String::from("$"), Arc::new(options));
                 let mut tracer = StripOuterSequenceSink::new(tracer);
                 accept_events(&mut tracer, events.iter().cloned()).unwrap();
                 let root = tracer.into_inner().to_field("root").unwrap();
@@ -108,6 +108,55 @@ test_example!(
     nulls = [false, false],
 );
 
+test_example!(
+    test_name = map_as_struct_int_keys,
+    field = GenericField::new("root", GenericDataType::Struct, false)
+        .with_strategy(Strategy::MapAsStruct)
+        .with_child(GenericField::new("-1", GenericDataType::U32, false))
+        .with_child(GenericField::new("2", GenericDataType::U32, false)),
+    ty = BTreeMap<i32, u32>,
+    values = [
+        btree_map!{ -1_i32 => 1_u32, 2_i32 => 2_u32 },
+        btree_map!{ 2_i32 => 3_u32, -1_i32 => 4_u32 },
+    ],
+    nulls = [false, false],
+);
+
+test_example!(
+    test_name = map_as_struct_bool_keys,
+    field = GenericField::new("root", GenericDataType::Struct, false)
+        .with_strategy(Strategy::MapAsStruct)
+        .with_child(GenericField::new("false", GenericDataType::U32, false))
+        .with_child(GenericField::new("true", GenericDataType::U32, false)),
+    ty = BTreeMap<bool, u32>,
+    values = [
+        btree_map!{ true => 1_u32, false => 2_u32 },
+        btree_map!{ false => 3_u32, true => 4_u32 },
+    ],
+    nulls = [false, false],
+);
+
+test_example!(
+    test_name = map_as_struct_unit_variant_keys,
+    field = GenericField::new("root", GenericDataType::Struct, false)
+        .with_strategy(Strategy::MapAsStruct)
+        .with_child(GenericField::new("A", GenericDataType::U32, false))
+        .with_child(GenericField::new("B", GenericDataType::U32, false)),
+    ty = BTreeMap<K, u32>,
+    values = [
+        btree_map!{ K::A => 1_u32, K::B => 2_u32 },
+        btree_map!{ K::B => 3_u32, K::A => 4_u32 },
+    ],
+    nulls = [false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        enum K {
+            A,
+            B,
+        }
+    },
+);
+
 test_example!(
     test_name = map_as_map,
     tracing_options = TracingOptions::default().map_as_struct(false),
@@ -1,4 +1,4 @@
-use super::macros::test_example;
+use super::macros::{test_error, test_example};
 
 test_example!(
     test_name = null,
@@ -252,6 +252,18 @@ test_example!(
     nulls = [false, true, true, false],
 );
 
+test_example!(
+    test_name = str_as_utf8,
+    tracing_options = TracingOptions::default().string_type(StringType::Utf8),
+    field = GenericField::new("root", GenericDataType::Utf8, true),
+    ty = Option<String>,
+    values = [Some(String::from("a")), None, Some(String::from("c"))],
+    nulls = [false, true, false],
+    define = {
+        use crate::schema::StringType;
+    },
+);
+
 test_example!(
     test_name = newtype_i64,
     field = GenericField::new("root", GenericDataType::I64, false),
@@ -289,3 +301,184 @@ test_example!(
     values = ['a', 'b', 'c'],
     nulls = [false, false, false],
 );
+
+test_example!(
+    test_name = coerce_numbers_ints_and_floats,
+    tracing_options = TracingOptions::default().coerce_numbers(true),
+    field = GenericField::new("root", GenericDataType::F64, false)
+        .with_strategy(Strategy::InconsistentTypes),
+    ty = Number,
+    values = [Number::Int(1), Number::Float(2.5), Number::Int(-3)],
+    expected_values = [Number::Float(1.0), Number::Float(2.5), Number::Float(-3.0)],
+    nulls = [false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Number {
+            Int(i32),
+            Float(f64),
+        }
+    },
+);
+
+test_example!(
+    test_name = coerce_numbers_widen_ints,
+    tracing_options = TracingOptions::default().coerce_numbers(true),
+    field = GenericField::new("root", GenericDataType::I32, false)
+        .with_strategy(Strategy::InconsistentTypes),
+    ty = Number,
+    values = [Number::Small(1), Number::Large(-1000), Number::Small(255)],
+    nulls = [false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Number {
+            Small(u16),
+            Large(i16),
+        }
+    },
+);
+
+test_example!(
+    test_name = coerce_numbers_small_u64_and_signed,
+    tracing_options = TracingOptions::default().coerce_numbers(true),
+    field = GenericField::new("root", GenericDataType::I64, false)
+        .with_strategy(Strategy::InconsistentTypes),
+    ty = Number,
+    values = [Number::Unsigned(5), Number::Signed(-3)],
+    nulls = [false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Number {
+            Unsigned(u64),
+            Signed(i8),
+        }
+    },
+);
+
+test_example!(
+    test_name = coerce_numbers_u64_and_signed,
+    tracing_options = TracingOptions::default().coerce_numbers(true),
+    field = GenericField::new("root", GenericDataType::F64, false)
+        .with_strategy(Strategy::InconsistentTypes),
+    ty = Number,
+    values = [Number::Unsigned(u64::MAX), Number::Signed(-1)],
+    expected_values = [Number::Float(u64::MAX as f64), Number::Float(-1.0)],
+    nulls = [false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Number {
+            Unsigned(u64),
+            Signed(i8),
+            Float(f64),
+        }
+    },
+);
+
+test_example!(
+    test_name = coerce_to_string,
+    tracing_options = TracingOptions::default().coerce_to_string(true),
+    field = GenericField::new("root", GenericDataType::LargeUtf8, false)
+        .with_strategy(Strategy::InconsistentTypes),
+    ty = Value,
+    values = [
+        Value::Str(String::from("hello")),
+        Value::Int(13),
+        Value::Flag(true)
+    ],
+    expected_values = [
+        Value::Str(String::from("hello")),
+        Value::Str(String::from("13")),
+        Value::Str(String::from("true"))
+    ],
+    nulls = [false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Value {
+            Str(String),
+            Int(i32),
+            Flag(bool),
+        }
+    },
+);
+
+test_example!(
+    test_name = coerce_to_string_dictionary,
+    test_deserialization = [],
+    tracing_options = TracingOptions::default()
+        .coerce_to_string(true)
+        .string_dictionary_encoding(true),
+    field = GenericField::new("root", GenericDataType::Dictionary, false)
+        .with_strategy(Strategy::InconsistentTypes)
+        .with_child(GenericField::new("key", GenericDataType::U32, false))
+        .with_child(GenericField::new(
+            "value",
+            GenericDataType::LargeUtf8,
+            false
+        )),
+    ty = Value,
+    values = [
+        Value::Str(String::from("13")),
+        Value::Int(13),
+        Value::Flag(true)
+    ],
+    nulls = [false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Value {
+            Str(String),
+            Int(i32),
+            Flag(bool),
+        }
+    },
+);
+
+test_error!(
+    test_name = inconsistent_numbers_without_coercion,
+    expected_error = "Cannot accept event F64",
+    block = {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        #[serde(untagged)]
+        enum Number {
+            Int(i32),
+            Float(f64),
+        }
+
+        serialize_into_field(
+            &[Number::Int(1), Number::Float(2.5)],
+            "root",
+            Default::default(),
+        )?;
+        Ok(())
+    },
+);
+
+test_error!(
+    test_name = ints_into_floats_without_coercion,
+    expected_error = "cannot accept I32",
+    block = {
+        use crate::internal::schema::{GenericDataType, GenericField};
+
+        let field = GenericField::new("root", GenericDataType::F64, false);
+        serialize_into_array(&Field::try_from(&field)?, &[1_i32, 2, 3])?;
+        Ok(())
+    },
+);
+
+test_error!(
+    test_name = primitives_into_strings_without_coercion,
+    expected_error = "cannot accept I32",
+    block = {
+        use crate::internal::schema::{GenericDataType, GenericField};
+
+        let field = GenericField::new("root", GenericDataType::LargeUtf8, false);
+        serialize_into_array(&Field::try_from(&field)?, &[1_i32, 2, 3])?;
+        Ok(())
+    },
+);
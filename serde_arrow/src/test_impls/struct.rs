@@ -150,3 +150,27 @@ test_events!(
         Event::EndSequence,
     ],
 );
+
+test_events!(
+    test_name = missing_record_fields,
+    fields = [
+        GenericField::new("foo", GenericDataType::U32, false),
+        GenericField::new("bar", GenericDataType::U8, true),
+    ],
+    events = [
+        Event::StartSequence,
+        Event::Item,
+        Event::StartStruct,
+        Event::Str("foo"),
+        Event::U32(0),
+        Event::EndStruct,
+        Event::Item,
+        Event::StartStruct,
+        Event::Str("bar"),
+        Event::U8(2),
+        Event::Str("foo"),
+        Event::U32(3),
+        Event::EndStruct,
+        Event::EndSequence,
+    ],
+);
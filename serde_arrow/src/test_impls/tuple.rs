@@ -45,3 +45,37 @@ test_example!(
     values = [((1,),), ((2,),)],
     nulls = [false, false],
 );
+
+test_example!(
+    test_name = tuple_as_list_f64,
+    tracing_options = TracingOptions::default().tuple_as_list(true),
+    field = GenericField::new("root", GenericDataType::LargeList, false)
+        .with_strategy(Strategy::TupleAsList)
+        .with_child(GenericField::new("element", GenericDataType::F64, false)),
+    ty = (f64, f64, f64),
+    values = [(1.0, 2.0, 3.0), (4.0, 5.0, 6.0)],
+    nulls = [false, false],
+);
+
+test_example!(
+    test_name = nullable_tuple_as_list_u32,
+    tracing_options = TracingOptions::default().tuple_as_list(true),
+    field = GenericField::new("root", GenericDataType::LargeList, true)
+        .with_strategy(Strategy::TupleAsList)
+        .with_child(GenericField::new("element", GenericDataType::U32, false)),
+    ty = Option<(u32, u32)>,
+    values = [Some((1, 2)), None, Some((3, 4))],
+    nulls = [false, true, false],
+);
+
+test_example!(
+    test_name = tuple_as_list_mixed_types_fall_back_to_struct,
+    tracing_options = TracingOptions::default().tuple_as_list(true),
+    field = GenericField::new("root", GenericDataType::Struct, false)
+        .with_strategy(Strategy::TupleAsStruct)
+        .with_child(GenericField::new("0", GenericDataType::U64, false))
+        .with_child(GenericField::new("1", GenericDataType::Bool, false)),
+    ty = (u64, bool),
+    values = [(1, true), (2, false)],
+    nulls = [false, false],
+);
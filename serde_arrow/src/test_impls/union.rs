@@ -185,3 +185,223 @@ test_error!(
         Ok(())
     },
 );
+
+test_example!(
+    test_name = internally_tagged_enum,
+    field = GenericField::new("root", GenericDataType::Struct, false)
+        .with_child(GenericField::new("type", GenericDataType::LargeUtf8, false))
+        .with_child(GenericField::new("a", GenericDataType::U32, true))
+        .with_child(GenericField::new("b", GenericDataType::LargeUtf8, true)),
+    ty = U,
+    values = [
+        U::A { a: 1 },
+        U::B {
+            b: String::from("hello")
+        },
+        U::C,
+        U::A { a: 2 },
+    ],
+    nulls = [false, false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(tag = "type")]
+        enum U {
+            A { a: u32 },
+            B { b: String },
+            C,
+        }
+    },
+);
+
+test_example!(
+    test_name = internally_tagged_enum_newtype_variants,
+    field = GenericField::new("root", GenericDataType::Struct, false)
+        .with_child(GenericField::new("type", GenericDataType::LargeUtf8, false))
+        .with_child(GenericField::new("x", GenericDataType::F64, true))
+        .with_child(GenericField::new("y", GenericDataType::Bool, true)),
+    ty = U,
+    values = [U::A(A { x: 1.0 }), U::B(B { y: true }),],
+    nulls = [false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(tag = "type")]
+        enum U {
+            A(A),
+            B(B),
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct A {
+            x: f64,
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct B {
+            y: bool,
+        }
+    },
+);
+
+test_example!(
+    test_name = adjacently_tagged_enum,
+    field = GenericField::new("root", GenericDataType::Struct, false)
+        .with_child(GenericField::new("t", GenericDataType::LargeUtf8, false))
+        .with_child(
+            GenericField::new("c", GenericDataType::Struct, true)
+                .with_child(GenericField::new("a", GenericDataType::U32, true))
+                .with_child(GenericField::new("b", GenericDataType::LargeUtf8, true))
+        ),
+    ty = U,
+    values = [
+        U::A { a: 1 },
+        U::C,
+        U::B {
+            b: String::from("hello")
+        },
+    ],
+    nulls = [false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(tag = "t", content = "c")]
+        enum U {
+            A { a: u32 },
+            B { b: String },
+            C,
+        }
+    },
+);
+
+test_example!(
+    test_name = untagged_enum,
+    tracing_options = TracingOptions::default().untagged_unions(true),
+    field = GenericField::new("root", GenericDataType::Union, false)
+        .with_strategy(Strategy::UntaggedUnion)
+        .with_child(GenericField::new("0", GenericDataType::U32, false))
+        .with_child(GenericField::new("1", GenericDataType::LargeUtf8, false))
+        .with_child(
+            GenericField::new("2", GenericDataType::Struct, false)
+                .with_child(GenericField::new("x", GenericDataType::F64, false))
+                .with_child(GenericField::new("y", GenericDataType::F64, false))
+        ),
+    ty = U,
+    values = [
+        U::Int(13),
+        U::Str(String::from("hello")),
+        U::Point { x: 1.0, y: 2.0 },
+        U::Int(21),
+    ],
+    nulls = [false, false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum U {
+            Int(u32),
+            Str(String),
+            Point { x: f64, y: f64 },
+        }
+    },
+);
+
+test_example!(
+    test_name = untagged_enum_nested,
+    tracing_options = TracingOptions::default().untagged_unions(true),
+    field = GenericField::new("root", GenericDataType::Struct, false).with_child(
+        GenericField::new("value", GenericDataType::Union, false)
+            .with_strategy(Strategy::UntaggedUnion)
+            .with_child(GenericField::new("0", GenericDataType::Bool, false))
+            .with_child(
+                GenericField::new("1", GenericDataType::LargeList, false)
+                    .with_child(GenericField::new("element", GenericDataType::I64, false))
+            )
+    ),
+    ty = S,
+    values = [
+        S {
+            value: U::Flag(true)
+        },
+        S {
+            value: U::Values(vec![1, 2, 3])
+        },
+        S {
+            value: U::Flag(false)
+        },
+    ],
+    nulls = [false, false, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct S {
+            value: U,
+        }
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum U {
+            Flag(bool),
+            Values(Vec<i64>),
+        }
+    },
+);
+
+test_example!(
+    test_name = untagged_enum_single_shape,
+    tracing_options = TracingOptions::default().untagged_unions(true),
+    field = GenericField::new("root", GenericDataType::U32, true),
+    ty = Option<U>,
+    values = [Some(U::A(1)), None, Some(U::A(2))],
+    nulls = [false, true, false],
+    define = {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum U {
+            A(u32),
+            B(String),
+        }
+    },
+);
+
+test_error!(
+    test_name = untagged_enum_unknown_shape,
+    expected_error =
+        "Serialization failed: no variant of the untagged union accepts values of shape Str",
+    block = {
+        use crate::schema::TracingOptions;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum U {
+            A(u32),
+            B(String),
+            C(bool),
+        }
+
+        let tracing_options = TracingOptions::default().untagged_unions(true);
+        let field = serialize_into_field(&[U::A(1), U::C(true)], "root", tracing_options).unwrap();
+
+        // NOTE: variant B was never encountered during tracing
+        serialize_into_array(&field, &[U::A(1), U::B(String::from("b"))])?;
+
+        Ok(())
+    },
+);
+
+test_error!(
+    test_name = internally_tagged_enum_conflicting_field_types,
+    expected_error = "Cannot accept event Str(\"b\") for primitive type U32",
+    block = {
+        use crate::schema::TracingOptions;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        #[serde(tag = "type")]
+        enum U {
+            A { x: u32 },
+            B { x: String },
+        }
+
+        let items = [U::A { x: 1 }, U::B { x: String::from("b") }];
+        serialize_into_field(&items, "root", TracingOptions::default())?;
+
+        Ok(())
+    },
+);
@@ -0,0 +1,480 @@
+//! Derive macros for `serde_arrow`
+//!
+//! This crate is re-exported by `serde_arrow` with the `derive` feature. See
+//! `serde_arrow::schema::ArrowSchema` for details.
+//!
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, parse_macro_input, parse_quote, Attribute, Data,
+    DeriveInput, Fields, LitStr, Result, Token,
+};
+
+/// Derive `ArrowField` and `ArrowSchema` for a struct with named fields
+///
+/// The type of each field is determined from its Rust type via `ArrowField`.
+/// `Option<T>` marks fields as nullable. The fields can be customized with the
+/// `arrow` attribute:
+///
+/// - `#[arrow(data_type = "Date64")]`: use the given primitive data type.
+///   Only data types without parameters are supported, e.g., timestamps with
+///   units or timezones (`"Timestamp(us, UTC)"`) are rejected. Use `Date64`
+///   with a strategy instead
+/// - `#[arrow(strategy = "UtcStrAsDate64")]`: set the strategy of the field.
+///   The name must be a variant of `serde_arrow::schema::Strategy`
+/// - `#[arrow(dictionary)]`: dictionary encode the values with `U32` keys
+///
+/// Field names respect `#[serde(rename = "...")]`, `#[serde(rename(serialize =
+/// "..."))]` and `#[serde(rename_all = "...")]` on the struct. Fields marked
+/// with `#[serde(skip)]` or `#[serde(skip_serializing)]` are ignored. Fields
+/// with `#[serde(with = "...")]` or `#[serde(serialize_with = "...")]` require
+/// an explicit `data_type`. `#[serde(flatten)]` and `#[serde(transparent)]` are
+/// not supported.
+///
+#[proc_macro_derive(ArrowSchema, attributes(arrow))]
+pub fn derive_arrow_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+const DATA_TYPES: &[(&str, &str)] = &[
+    ("Null", "Null"),
+    ("Bool", "Bool"),
+    ("Boolean", "Bool"),
+    ("I8", "I8"),
+    ("Int8", "I8"),
+    ("I16", "I16"),
+    ("Int16", "I16"),
+    ("I32", "I32"),
+    ("Int32", "I32"),
+    ("I64", "I64"),
+    ("Int64", "I64"),
+    ("U8", "U8"),
+    ("UInt8", "U8"),
+    ("U16", "U16"),
+    ("UInt16", "U16"),
+    ("U32", "U32"),
+    ("UInt32", "U32"),
+    ("U64", "U64"),
+    ("UInt64", "U64"),
+    ("F16", "F16"),
+    ("Float16", "F16"),
+    ("F32", "F32"),
+    ("Float32", "F32"),
+    ("F64", "F64"),
+    ("Float64", "F64"),
+    ("Utf8", "Utf8"),
+    ("LargeUtf8", "LargeUtf8"),
+    ("Date32", "Date32"),
+    ("Date64", "Date64"),
+];
+
+/// The rename rules supported by `#[serde(rename_all = "...")]`
+const RENAME_RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+#[derive(Default)]
+struct ArrowAttributes {
+    data_type: Option<syn::Ident>,
+    strategy: Option<syn::Ident>,
+    dictionary: bool,
+}
+
+#[derive(Default)]
+struct SerdeAttributes {
+    rename: Option<String>,
+    skip: bool,
+    /// The span of a `with` or `serialize_with` attribute
+    with: Option<Span>,
+}
+
+#[derive(Default)]
+struct SerdeContainerAttributes {
+    rename_all: Option<String>,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(unsupported_input(&input)),
+        },
+        _ => return Err(unsupported_input(&input)),
+    };
+
+    let container_attrs = parse_serde_container_attributes(&input.attrs)?;
+
+    let mut children = Vec::new();
+    let mut generics = input.generics.clone();
+    for field in fields {
+        let serde_attrs = parse_serde_attributes(&field.attrs)?;
+        if serde_attrs.skip {
+            continue;
+        }
+        let arrow_attrs = parse_arrow_attributes(&field.attrs)?;
+        if let (Some(span), None) = (serde_attrs.with, &arrow_attrs.data_type) {
+            return Err(syn::Error::new(
+                span,
+                "fields with custom serialization require #[arrow(data_type = \"...\")]",
+            ));
+        }
+
+        let ty = &field.ty;
+        let name = match serde_attrs.rename {
+            Some(name) => name,
+            None => {
+                let name = field.ident.as_ref().unwrap().unraw().to_string();
+                match &container_attrs.rename_all {
+                    Some(rule) => apply_rename_rule(rule, &name),
+                    None => name,
+                }
+            }
+        };
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: ::serde_arrow::schema::ArrowField));
+
+        let mut overwrites = Vec::new();
+        if let Some(data_type) = arrow_attrs.data_type {
+            overwrites.push(quote! {
+                child.data_type = ::serde_arrow::schema::GenericDataType::#data_type;
+                child.children.clear();
+            });
+        }
+        if arrow_attrs.dictionary {
+            overwrites.push(quote! {
+                child = ::serde_arrow::schema::GenericField::new(
+                    #name,
+                    ::serde_arrow::schema::GenericDataType::Dictionary,
+                    child.nullable,
+                )
+                .with_child(::serde_arrow::schema::GenericField::new(
+                    "key",
+                    ::serde_arrow::schema::GenericDataType::U32,
+                    false,
+                ))
+                .with_child(::serde_arrow::schema::GenericField::new(
+                    "value",
+                    child.data_type,
+                    false,
+                ));
+            });
+        }
+        if let Some(strategy) = arrow_attrs.strategy {
+            overwrites.push(quote! {
+                child.strategy = ::std::option::Option::Some(
+                    ::serde_arrow::schema::Strategy::#strategy,
+                );
+            });
+        }
+
+        children.push(quote! {
+            {
+                #[allow(unused_mut)]
+                let mut child = <#ty as ::serde_arrow::schema::ArrowField>::arrow_field(#name);
+                #(#overwrites)*
+                child
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::serde_arrow::schema::ArrowField for #ident #ty_generics #where_clause {
+            fn arrow_field(name: &str) -> ::serde_arrow::schema::GenericField {
+                let mut field = ::serde_arrow::schema::GenericField::new(
+                    name,
+                    ::serde_arrow::schema::GenericDataType::Struct,
+                    false,
+                );
+                #(field.children.push(#children);)*
+                field
+            }
+        }
+
+        impl #impl_generics ::serde_arrow::schema::ArrowSchema for #ident #ty_generics #where_clause {
+            fn arrow_schema() -> ::serde_arrow::schema::SerdeArrowSchema {
+                let field = <Self as ::serde_arrow::schema::ArrowField>::arrow_field("");
+                ::serde_arrow::schema::SerdeArrowSchema {
+                    fields: field.children,
+                }
+            }
+        }
+    })
+}
+
+fn unsupported_input(input: &DeriveInput) -> syn::Error {
+    syn::Error::new_spanned(
+        &input.ident,
+        "ArrowSchema can only be derived for structs with named fields",
+    )
+}
+
+fn parse_arrow_attributes(attrs: &[Attribute]) -> Result<ArrowAttributes> {
+    let mut result = ArrowAttributes::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("arrow") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("data_type") {
+                let value: LitStr = meta.value()?.parse()?;
+                if value.value().contains('(') {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!(
+                            concat!(
+                                "unsupported data type {:?}, data types with parameters ",
+                                "(e.g., timestamps with units or timezones) are not supported",
+                            ),
+                            value.value(),
+                        ),
+                    ));
+                }
+                let Some((_, variant)) = DATA_TYPES.iter().find(|(name, _)| *name == value.value())
+                else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        format!(
+                            "unsupported data type {:?}, expected one of: {}",
+                            value.value(),
+                            join_names(DATA_TYPES.iter().map(|(name, _)| *name)),
+                        ),
+                    ));
+                };
+                result.data_type = Some(syn::Ident::new(variant, Span::call_site()));
+                Ok(())
+            } else if meta.path.is_ident("strategy") {
+                // NOTE: the strategy is emitted as `Strategy::<name>` with the span of the
+                // literal, unknown names are rejected by the compiler
+                let value: LitStr = meta.value()?.parse()?;
+                result.strategy = Some(value.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("dictionary") {
+                result.dictionary = true;
+                Ok(())
+            } else {
+                Err(meta
+                    .error("unknown arrow attribute, expected data_type, strategy or dictionary"))
+            }
+        })?;
+    }
+
+    Ok(result)
+}
+
+fn parse_serde_attributes(attrs: &[Attribute]) -> Result<SerdeAttributes> {
+    let mut result = SerdeAttributes::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                if let Some(value) = parse_serialize_name(&meta)? {
+                    result.rename = Some(value.value());
+                }
+            } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                result.skip = true;
+            } else if meta.path.is_ident("with") || meta.path.is_ident("serialize_with") {
+                let value: LitStr = meta.value()?.parse()?;
+                result.with = Some(value.span());
+            } else if meta.path.is_ident("flatten") {
+                return Err(meta.error("flattened fields are not supported by ArrowSchema"));
+            } else {
+                skip_serde_attribute(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(result)
+}
+
+fn parse_serde_container_attributes(attrs: &[Attribute]) -> Result<SerdeContainerAttributes> {
+    let mut result = SerdeContainerAttributes::default();
+
+    for attr in attrs {
+        if !attr.path().is_ident("serde") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                if let Some(value) = parse_serialize_name(&meta)? {
+                    if !RENAME_RULES.contains(&value.value().as_str()) {
+                        return Err(syn::Error::new(
+                            value.span(),
+                            format!(
+                                "unknown rename rule {:?}, expected one of: {}",
+                                value.value(),
+                                join_names(RENAME_RULES.iter().copied()),
+                            ),
+                        ));
+                    }
+                    result.rename_all = Some(value.value());
+                }
+            } else if meta.path.is_ident("transparent") {
+                return Err(meta.error("transparent structs are not supported by ArrowSchema"));
+            } else {
+                skip_serde_attribute(&meta)?;
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(result)
+}
+
+/// Parse the serialization name of `name = "..."` or `name(serialize = "...")`
+///
+/// Returns `None`, if only the deserialization name is given.
+///
+fn parse_serialize_name(meta: &ParseNestedMeta) -> Result<Option<LitStr>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse()?));
+    }
+
+    let mut result = None;
+    meta.parse_nested_meta(|meta| {
+        if meta.path.is_ident("serialize") {
+            result = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("deserialize") {
+            let _: LitStr = meta.value()?.parse()?;
+        } else {
+            return Err(meta.error("expected serialize or deserialize"));
+        }
+        Ok(())
+    })?;
+    Ok(result)
+}
+
+/// Ignore serde attributes that do not affect the schema, e.g., `default = "..."`
+fn skip_serde_attribute(meta: &ParseNestedMeta) -> Result<()> {
+    if meta.input.peek(Token![=]) {
+        let _: syn::Expr = meta.value()?.parse()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let _content;
+        syn::parenthesized!(_content in meta.input);
+    }
+    Ok(())
+}
+
+/// Apply a serde rename rule to a field name, following `serde_derive`
+///
+/// Field names are expected to be in snake case.
+///
+fn apply_rename_rule(rule: &str, name: &str) -> String {
+    match rule {
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => name.to_ascii_uppercase(),
+        "PascalCase" => to_pascal_case(name),
+        "camelCase" => {
+            let pascal = to_pascal_case(name);
+            let mut chars = pascal.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => pascal,
+            }
+        }
+        "kebab-case" => name.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => name.to_ascii_uppercase().replace('_', "-"),
+        _ => name.to_owned(),
+    }
+}
+
+fn to_pascal_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut capitalize = true;
+    for ch in name.chars() {
+        if ch == '_' {
+            capitalize = true;
+        } else if capitalize {
+            result.push(ch.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            result.push(ch);
+        }
+    }
+    result
+}
+
+fn join_names<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    names.collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::expand;
+
+    fn expand_error(input: syn::DeriveInput) -> String {
+        match expand(input) {
+            Ok(_) => panic!("expected an error"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn unsupported_serde_attributes() {
+        let err = expand_error(parse_quote! {
+            struct Record {
+                #[serde(flatten)]
+                inner: Inner,
+            }
+        });
+        assert!(err.contains("flattened fields"), "{err}");
+
+        let err = expand_error(parse_quote! {
+            #[serde(transparent)]
+            struct Record {
+                inner: Inner,
+            }
+        });
+        assert!(err.contains("transparent structs"), "{err}");
+
+        let err = expand_error(parse_quote! {
+            struct Record {
+                #[serde(with = "chrono::serde::ts_milliseconds")]
+                timestamp: DateTime<Utc>,
+            }
+        });
+        assert!(err.contains("custom serialization"), "{err}");
+
+        let err = expand_error(parse_quote! {
+            struct Record {
+                #[serde(serialize_with = "serialize_timestamp")]
+                timestamp: DateTime<Utc>,
+            }
+        });
+        assert!(err.contains("custom serialization"), "{err}");
+    }
+
+    #[test]
+    fn custom_serialization_with_data_type() {
+        let input = parse_quote! {
+            struct Record {
+                #[serde(with = "chrono::serde::ts_milliseconds")]
+                #[arrow(data_type = "Date64")]
+                timestamp: DateTime<Utc>,
+            }
+        };
+        assert!(expand(input).is_ok());
+    }
+}
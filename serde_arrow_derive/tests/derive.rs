@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_arrow::{
+    _impl::arrow2::datatypes::{DataType, Field, IntegerType},
+    arrow2::{deserialize_from_arrays, serialize_into_arrays, serialize_into_fields},
+    schema::{ArrowField, ArrowSchema, GenericDataType, Strategy, TracingOptions},
+};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ArrowSchema)]
+struct Record {
+    a: Option<u32>,
+    b: String,
+    c: Vec<f64>,
+    d: Nested,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, ArrowSchema)]
+struct Nested {
+    flag: bool,
+    values: Option<Vec<i16>>,
+}
+
+#[test]
+fn matches_traced_schema() {
+    let items = vec![Record {
+        a: Some(1),
+        b: String::from("foo"),
+        c: vec![1.0],
+        d: Nested {
+            flag: true,
+            values: Some(vec![2]),
+        },
+    }];
+
+    let fields = Record::arrow_schema().to_arrow2_fields().unwrap();
+    let traced = serialize_into_fields(&items, TracingOptions::default()).unwrap();
+    assert_eq!(fields, traced);
+
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    let roundtripped: Vec<Record> = deserialize_from_arrays(&fields, &arrays).unwrap();
+    assert_eq!(roundtripped, items);
+}
+
+#[test]
+fn attributes() {
+    #[derive(Serialize, ArrowSchema)]
+    struct Record {
+        #[arrow(data_type = "Date64", strategy = "UtcStrAsDate64")]
+        dt: String,
+        #[arrow(dictionary)]
+        label: Option<String>,
+        #[arrow(data_type = "Utf8", dictionary)]
+        small_label: String,
+        #[serde(rename = "renamed")]
+        value: i64,
+        #[serde(skip)]
+        #[allow(unused)]
+        ignored: u8,
+    }
+
+    let fields = Record::arrow_schema().to_arrow2_fields().unwrap();
+    let expected = vec![
+        Field::new("dt", DataType::Date64, false).with_metadata(Strategy::UtcStrAsDate64.into()),
+        Field::new(
+            "label",
+            DataType::Dictionary(IntegerType::UInt32, Box::new(DataType::LargeUtf8), false),
+            true,
+        ),
+        Field::new(
+            "small_label",
+            DataType::Dictionary(IntegerType::UInt32, Box::new(DataType::Utf8), false),
+            false,
+        ),
+        Field::new("renamed", DataType::Int64, false),
+    ];
+    assert_eq!(fields, expected);
+
+    let items = [Record {
+        dt: String::from("2023-01-31T12:00:00Z"),
+        label: Some(String::from("a")),
+        small_label: String::from("b"),
+        value: 3,
+        ignored: 0,
+    }];
+    let arrays = serialize_into_arrays(&fields, &items).unwrap();
+    assert_eq!(arrays.len(), 4);
+}
+
+#[test]
+fn maps_and_generics() {
+    #[derive(Serialize, ArrowSchema)]
+    struct Wrapper<T> {
+        value: T,
+        map: HashMap<String, T>,
+    }
+
+    let schema = Wrapper::<u8>::arrow_schema();
+    assert_eq!(schema.fields[0].data_type, GenericDataType::U8);
+    assert_eq!(schema.fields[1].data_type, GenericDataType::Map);
+    schema.validate().unwrap();
+
+    let field = Wrapper::<Option<bool>>::arrow_field("root");
+    assert_eq!(field.data_type, GenericDataType::Struct);
+    assert!(field.children[0].nullable);
+}
+
+#[test]
+fn renamed_fields_match_traced_schema() {
+    #[derive(Serialize, ArrowSchema)]
+    #[serde(rename_all = "camelCase")]
+    struct CamelCase {
+        first_value: u8,
+        #[serde(rename(serialize = "second", deserialize = "ignored"))]
+        second_value: u16,
+        #[serde(rename(deserialize = "ignored"))]
+        third_value: u32,
+        r#type: bool,
+    }
+
+    #[derive(Serialize, ArrowSchema)]
+    #[serde(rename_all(serialize = "SCREAMING-KEBAB-CASE"))]
+    struct ScreamingKebabCase {
+        first_value: u8,
+    }
+
+    let items = [CamelCase {
+        first_value: 1,
+        second_value: 2,
+        third_value: 3,
+        r#type: true,
+    }];
+    let fields = CamelCase::arrow_schema().to_arrow2_fields().unwrap();
+    let traced = serialize_into_fields(&items, TracingOptions::default()).unwrap();
+    assert_eq!(fields, traced);
+
+    let names = fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(names, ["firstValue", "second", "thirdValue", "type"]);
+
+    let items = [ScreamingKebabCase { first_value: 1 }];
+    let fields = ScreamingKebabCase::arrow_schema()
+        .to_arrow2_fields()
+        .unwrap();
+    let traced = serialize_into_fields(&items, TracingOptions::default()).unwrap();
+    assert_eq!(fields, traced);
+    assert_eq!(fields[0].name, "FIRST-VALUE");
+}
@@ -7,6 +7,7 @@ arg = lambda *a, **kw: __effect(lambda d: d.setdefault("@arg", []).append((a, kw
 all_arrow_features = ["arrow-35", "arrow-36", "arrow-37", "arrow-38", "arrow-39"]
 all_arrow2_features = ["arrow2-0-16", "arrow2-0-17"]
 default_features = f"{all_arrow2_features[-1]},{all_arrow_features[-1]}"
+test_features = f"{default_features},json"
 
 CHECKS_PLACEHOLDER = "<<< checks >>>"
 
@@ -118,11 +119,11 @@ def _generate_workflow_check_steps():
 
     yield {
         "name": "Build",
-        "run": f"cargo build --features {default_features}",
+        "run": f"cargo build --features {test_features}",
     }
     yield {
         "name": "Test",
-        "run": f"cargo test --features {default_features}",
+        "run": f"cargo test --features {test_features}",
     }
 
 
@@ -142,7 +143,7 @@ def lint():
             "--features",
             arrow2_feature,
         )
-    cargo("clippy", "--features", default_features)
+    cargo("clippy", "--features", test_features)
 
 
 @cmd(help="Run the example")
@@ -158,7 +159,7 @@ def test(backtrace=False, full=False):
     import os
 
     if not full:
-        flag_combinations = [["--features", default_features]]
+        flag_combinations = [["--features", test_features]]
 
     else:
         flag_combinations = []
@@ -434,7 +435,7 @@ def doc(private=False):
     cargo(
         "doc",
         "--features",
-        default_features,
+        test_features,
         *(["--document-private-items"] if private else []),
         cwd=self_path / "serde_arrow",
     )